@@ -19,6 +19,7 @@ use rsgui::fill::Fill::Enabled;
 use rsgui::hbox::HBoxPar;
 use rsgui::slider::SliderPar;
 use rsgui::mouse::MouseState;
+use rsgui::keyboard::KeyboardState;
 
 fn main() {
     let (mut rl, thread) = raylib::init()
@@ -189,6 +190,7 @@ fn main() {
 
     let mut camera = Camera2D::default();
     let mut mouse_state = MouseState::new();
+    let mut keyboard_state = KeyboardState::new();
 
 
     while !rl.window_should_close() {
@@ -206,12 +208,16 @@ fn main() {
 
         {
             mouse_state.update(&d);
+            keyboard_state.update(&d);
+            gui.update_time(d.get_frame_time());
 
 
             d.clear_background(Color::WHITE);
 
+            gui.remove_closed_layers();
             gui.update_states(&mouse_state.mouse_position(), &offset);
             gui.handle_events(&mouse_state, &offset);
+            gui.handle_key_events(&keyboard_state);
             gui.layout(&screen_size);
             gui.render(&mut d, &offset);
         }
@@ -6,6 +6,11 @@ pub enum VAlignment {
     Top,
     Center,
     Bottom,
+    /// the baselines of the text of the children of an `HBoxPar` are aligned,
+    /// elsewhere it is the same as `Top`
+    Baseline,
+    /// the widget takes the whole height it is given, like when it fills the height
+    Stretch,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -13,6 +18,9 @@ pub enum HAlignment {
     Left,
     Middle,
     Right,
+    /// the widget takes the whole width it is given, like when it fills the width.
+    /// A text aligned this way starts on the left
+    Stretch,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -33,7 +41,7 @@ impl Alignment {
 impl VAlignment {
     pub fn shift_factor(&self) -> f32 {
         match self {
-            VAlignment::Top => 0.,
+            VAlignment::Top | VAlignment::Baseline | VAlignment::Stretch => 0.,
             VAlignment::Center => -0.5,
             VAlignment::Bottom => -1.0,
         }
@@ -45,7 +53,39 @@ impl HAlignment {
         match self {
             HAlignment::Right => -1.0,
             HAlignment::Middle => -0.5,
-            HAlignment::Left => 0.0,
+            HAlignment::Left | HAlignment::Stretch => 0.0,
+        }
+    }
+}
+
+/// How the children of a box are spread along its main axis when they do not fill it
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Justify {
+    Start,
+    End,
+    Center,
+    /// the free space goes between the children
+    SpaceBetween,
+    /// each child gets the same free space on both sides
+    SpaceAround,
+    /// the same free space before, between and after the children
+    SpaceEvenly,
+}
+
+impl Justify {
+    /// The position of the first child and the space added between two children,
+    /// for `nb_children` children leaving `free_space` empty
+    pub(crate) fn distribute(&self, free_space: f32, nb_children: usize) -> (f32, f32) {
+        let free_space = free_space.max(0.0);
+        let nb = nb_children.max(1) as f32;
+        match self {
+            Justify::Start => (0.0, 0.0),
+            Justify::End => (free_space, 0.0),
+            Justify::Center => (free_space * 0.5, 0.0),
+            Justify::SpaceBetween if nb_children > 1 => (0.0, free_space / (nb - 1.0)),
+            Justify::SpaceBetween => (0.0, 0.0),
+            Justify::SpaceAround => (free_space / nb * 0.5, free_space / nb),
+            Justify::SpaceEvenly => (free_space / (nb + 1.0), free_space / (nb + 1.0)),
         }
     }
 }
@@ -54,4 +94,33 @@ impl Default for Alignment {
     fn default() -> Self {
         Alignment{vertical:Center,horizontal:Middle}
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::Justify;
+
+    #[test]
+    fn places_the_free_space_before_or_around_the_children() {
+        assert_eq!(Justify::Start.distribute(60.0, 3), (0.0, 0.0));
+        assert_eq!(Justify::End.distribute(60.0, 3), (60.0, 0.0));
+        assert_eq!(Justify::Center.distribute(60.0, 3), (30.0, 0.0));
+    }
+
+    #[test]
+    fn spreads_the_free_space_between_the_children() {
+        assert_eq!(Justify::SpaceBetween.distribute(60.0, 3), (0.0, 30.0));
+        assert_eq!(Justify::SpaceAround.distribute(60.0, 3), (10.0, 20.0));
+        assert_eq!(Justify::SpaceEvenly.distribute(60.0, 3), (15.0, 15.0));
+    }
+
+    #[test]
+    fn keeps_a_single_child_at_the_start_with_space_between() {
+        assert_eq!(Justify::SpaceBetween.distribute(60.0, 1), (0.0, 0.0));
+    }
+
+    #[test]
+    fn ignores_a_negative_free_space() {
+        assert_eq!(Justify::End.distribute(-20.0, 2), (0.0, 0.0));
+        assert_eq!(Justify::SpaceBetween.distribute(-20.0, 2), (0.0, 0.0));
+    }
+}
@@ -0,0 +1,289 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+
+use raylib::prelude::*;
+
+use crate::gui::Gui;
+use crate::mouse::{MouseButtonState, MouseState};
+use crate::size::Size;
+use crate::text_style::TextStyle;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+const CANVAS_DEFAULT_WIDTH: f32 = 100.0;
+const CANVAS_DEFAULT_HEIGHT: f32 = 100.0;
+
+/// Draw the content of a canvas. The coordinates are local to the content of the canvas,
+/// whose size is provided, and the drawing is clipped to it
+pub type CanvasPainter = Box<dyn Fn(&mut dyn CanvasDraw, Size)>;
+
+/// React to the mouse over a canvas
+pub type CanvasMouseHandler = Box<dyn Fn(&CanvasMouse)>;
+
+/// The drawing operations given to the painter of a canvas, with the origin at the
+/// top left corner of the content of the canvas
+pub trait CanvasDraw {
+    fn draw_pixel(&mut self, position: Vector2, color: Color);
+    fn draw_line(&mut self, start: Vector2, end: Vector2, thickness: f32, color: Color);
+    /// Draw the segments joining the points in sequence
+    fn draw_line_strip(&mut self, points: &[Vector2], color: Color);
+    fn draw_rectangle(&mut self, rectangle: Rectangle, color: Color);
+    fn draw_rectangle_lines(&mut self, rectangle: Rectangle, thickness: i32, color: Color);
+    fn draw_rectangle_rounded(&mut self, rectangle: Rectangle, roundness: f32, segments: i32, color: Color);
+    fn draw_circle(&mut self, center: Vector2, radius: f32, color: Color);
+    fn draw_circle_lines(&mut self, center: Vector2, radius: f32, color: Color);
+    /// The vertices are in counter-clockwise order
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color);
+    /// Draw the text with a style of the gui, the position being its top left corner
+    fn draw_text(&mut self, text_style: &TextStyle, text: &str, position: Vector2);
+    /// Draw the text with the default font of raylib
+    fn draw_default_text(&mut self, text: &str, position: Vector2, font_size: i32, color: Color);
+}
+
+/// The canvas drawing operations on the draw handle of the gui, translated to the content of the canvas
+struct CanvasDrawHandle<'a, D: RaylibDraw> {
+    d: &'a mut D,
+    origin: Vector2,
+}
+
+impl<D: RaylibDraw> CanvasDrawHandle<'_, D> {
+    fn to_screen(&self, position: Vector2) -> Vector2 {
+        Vector2::new(position.x + self.origin.x, position.y + self.origin.y)
+    }
+
+    fn to_screen_rectangle(&self, rectangle: Rectangle) -> Rectangle {
+        Rectangle::new(rectangle.x + self.origin.x, rectangle.y + self.origin.y, rectangle.width, rectangle.height)
+    }
+}
+
+impl<D: RaylibDraw> CanvasDraw for CanvasDrawHandle<'_, D> {
+    fn draw_pixel(&mut self, position: Vector2, color: Color) {
+        let position = self.to_screen(position);
+        self.d.draw_pixel_v(position, color);
+    }
+
+    fn draw_line(&mut self, start: Vector2, end: Vector2, thickness: f32, color: Color) {
+        let start = self.to_screen(start);
+        let end = self.to_screen(end);
+        self.d.draw_line_ex(start, end, thickness, color);
+    }
+
+    fn draw_line_strip(&mut self, points: &[Vector2], color: Color) {
+        let points: Vec<Vector2> = points.iter().map(|point| self.to_screen(*point)).collect();
+        self.d.draw_line_strip(&points, color);
+    }
+
+    fn draw_rectangle(&mut self, rectangle: Rectangle, color: Color) {
+        let rectangle = self.to_screen_rectangle(rectangle);
+        self.d.draw_rectangle_rec(rectangle, color);
+    }
+
+    fn draw_rectangle_lines(&mut self, rectangle: Rectangle, thickness: i32, color: Color) {
+        let rectangle = self.to_screen_rectangle(rectangle);
+        self.d.draw_rectangle_lines_ex(rectangle, thickness, color);
+    }
+
+    fn draw_rectangle_rounded(&mut self, rectangle: Rectangle, roundness: f32, segments: i32, color: Color) {
+        let rectangle = self.to_screen_rectangle(rectangle);
+        self.d.draw_rectangle_rounded(rectangle, roundness, segments, color);
+    }
+
+    fn draw_circle(&mut self, center: Vector2, radius: f32, color: Color) {
+        let center = self.to_screen(center);
+        self.d.draw_circle_v(center, radius, color);
+    }
+
+    fn draw_circle_lines(&mut self, center: Vector2, radius: f32, color: Color) {
+        let center = self.to_screen(center);
+        self.d.draw_circle_lines(center.x.round() as i32, center.y.round() as i32, radius, color);
+    }
+
+    fn draw_triangle(&mut self, v1: Vector2, v2: Vector2, v3: Vector2, color: Color) {
+        let (v1, v2, v3) = (self.to_screen(v1), self.to_screen(v2), self.to_screen(v3));
+        self.d.draw_triangle(v1, v2, v3, color);
+    }
+
+    fn draw_text(&mut self, text_style: &TextStyle, text: &str, position: Vector2) {
+        let position = self.to_screen(position);
+        text_style.draw_text(self.d, text, &position);
+    }
+
+    fn draw_default_text(&mut self, text: &str, position: Vector2, font_size: i32, color: Color) {
+        let position = self.to_screen(position);
+        self.d.draw_text(text, position.x.round() as i32, position.y.round() as i32, font_size, color);
+    }
+}
+
+/// The state of a mouse button seen by a canvas
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CanvasButton {
+    pressed: bool,
+    down: bool,
+    released: bool,
+}
+
+impl CanvasButton {
+    fn from_state(state: &MouseButtonState) -> Self {
+        Self { pressed: state.is_pressed(), down: state.is_down(), released: state.is_released() }
+    }
+
+    /// the button went down since the last frame
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+    pub fn is_down(&self) -> bool {
+        self.down
+    }
+    /// the button went up since the last frame
+    pub fn is_released(&self) -> bool {
+        self.released
+    }
+}
+
+/// The mouse seen by a canvas, forwarded each frame while the mouse is over the canvas
+/// or while a press started on the canvas is held
+#[derive(Clone, Debug)]
+pub struct CanvasMouse {
+    position: Vector2,
+    inside: bool,
+    left: CanvasButton,
+    middle: CanvasButton,
+    right: CanvasButton,
+    wheel_move: f32,
+    double_clicked: bool,
+}
+
+impl CanvasMouse {
+    /// the position of the mouse relative to the top left corner of the content of the canvas
+    pub fn position(&self) -> Vector2 {
+        self.position
+    }
+    /// false when the mouse left the canvas while a button pressed on it is held
+    pub fn is_inside(&self) -> bool {
+        self.inside
+    }
+    pub fn left(&self) -> &CanvasButton {
+        &self.left
+    }
+    pub fn middle(&self) -> &CanvasButton {
+        &self.middle
+    }
+    pub fn right(&self) -> &CanvasButton {
+        &self.right
+    }
+    pub fn wheel_move(&self) -> f32 {
+        self.wheel_move
+    }
+    pub fn is_double_clicked(&self) -> bool {
+        self.double_clicked
+    }
+}
+
+/// A widget reserving space in the layout and leaving its content to a user painter,
+/// like small plots or previews. The mouse is forwarded to a user handler in the
+/// same local coordinates
+pub struct CanvasPar {
+    widget_data: WidgetData,
+    painter: RefCell<Option<CanvasPainter>>,
+    mouse_handler: RefCell<Option<CanvasMouseHandler>>,
+    /// a press started on the canvas and the button is still held
+    captured: Cell<bool>,
+}
+
+impl Deref for CanvasPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl CanvasPar {
+    pub fn new() -> Self {
+        let par = Self {
+            widget_data: WidgetData::new(),
+            painter: RefCell::new(None),
+            mouse_handler: RefCell::new(None),
+            captured: Cell::new(false),
+        };
+        par.set_hooverable(true);
+        par
+    }
+
+    pub fn set_painter(&self, painter: impl Fn(&mut dyn CanvasDraw, Size) + 'static) -> &CanvasPar {
+        self.painter.replace(Some(Box::new(painter)));
+        self
+    }
+
+    pub fn set_mouse_handler(&self, mouse_handler: impl Fn(&CanvasMouse) + 'static) -> &CanvasPar {
+        self.mouse_handler.replace(Some(Box::new(mouse_handler)));
+        self
+    }
+}
+
+impl WidgetSpecific for CanvasPar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, _gui: &Gui) -> Size {
+        let size = Size::new(CANVAS_DEFAULT_WIDTH, CANVAS_DEFAULT_HEIGHT);
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&size.with_padding(&self.padding()));
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {}
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+
+        let inside = self.get_hoover_state();
+        let any_down = mouse_state.left().is_down() || mouse_state.middle().is_down() || mouse_state.right().is_down();
+        let any_pressed = mouse_state.left().is_pressed() || mouse_state.middle().is_pressed() || mouse_state.right().is_pressed();
+        let was_captured = self.captured.get();
+        self.captured.set(any_down && ((inside && any_pressed) || was_captured));
+
+        // the frame of the release is forwarded too, even outside of the canvas
+        if !inside && !was_captured {
+            return;
+        }
+
+        if let Some(mouse_handler) = self.mouse_handler.borrow().as_ref() {
+            let content_layout = self.content_layout();
+            let mouse_position = mouse_state.mouse_position();
+            mouse_handler(&CanvasMouse {
+                position: Vector2::new(mouse_position.x - offset.x - content_layout.x, mouse_position.y - offset.y - content_layout.y),
+                inside,
+                left: CanvasButton::from_state(mouse_state.left()),
+                middle: CanvasButton::from_state(mouse_state.middle()),
+                right: CanvasButton::from_state(mouse_state.right()),
+                wheel_move: mouse_state.wheel_move(),
+                double_clicked: mouse_state.is_double_clicked(),
+            });
+        }
+    }
+
+    fn render_my_visual(&self, gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let painter = self.painter.borrow();
+        let painter = match painter.as_ref() {
+            None => return,
+            Some(painter) => painter,
+        };
+
+        let mut content_layout = self.content_layout();
+        content_layout.x += offset.x;
+        content_layout.y += offset.y;
+
+        gui.push_clip(&content_layout);
+        let mut canvas_draw = CanvasDrawHandle { d, origin: Vector2::new(content_layout.x, content_layout.y) };
+        painter(&mut canvas_draw, Size::new(content_layout.width, content_layout.height));
+        gui.pop_clip();
+    }
+}
@@ -0,0 +1,530 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::ops::Deref;
+
+use raylib::prelude::*;
+
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+const CHART_DEFAULT_WIDTH: f32 = 300.0;
+const CHART_DEFAULT_HEIGHT: f32 = 200.0;
+const CHART_TICK_LENGTH: f32 = 4.0;
+const CHART_LABEL_SPACING: f32 = 2.0;
+/// the number of ticks aimed at on each axis, the actual one depends on the rounding of the step
+const CHART_TICK_TARGET: f32 = 5.0;
+/// the most ticks an axis can have, whatever the range of the data
+const CHART_MAX_TICKS: usize = 1000;
+const CHART_AXIS_COLOR: Color = Color::DARKGRAY;
+const CHART_GRID_COLOR: Color = Color::new(200, 200, 200, 255);
+const CHART_LEGEND_SWATCH_SIZE: f32 = 10.0;
+const CHART_LEGEND_SPACING: f32 = 10.0;
+/// the part of the space between two points taken by the bars at one x
+const CHART_BAR_RATIO: f32 = 0.8;
+const CHART_HOOVER_RADIUS: f32 = 4.0;
+/// the maximal distance in pixels between the mouse and the point shown by the readout
+const CHART_HOOVER_DISTANCE: f32 = 30.0;
+const CHART_READOUT_MARGIN: f32 = 4.0;
+const CHART_READOUT_COLOR: Color = Color::new(255, 255, 225, 230);
+
+/// How a series is drawn
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SeriesKind {
+    /// the points joined by segments
+    Line,
+    /// a bar from zero to each point, side by side with the bars of the other series
+    Bar,
+}
+
+struct ChartSeries {
+    name: String,
+    kind: SeriesKind,
+    color: Color,
+    points: VecDeque<Vector2>,
+    /// the oldest points are dropped beyond this count
+    max_points: Option<usize>,
+    name_size: Size,
+}
+
+/// The graduations of an axis
+#[derive(Default)]
+struct AxisTicks {
+    min: f32,
+    max: f32,
+    values: Vec<f32>,
+    labels: Vec<(String, Size)>,
+}
+
+/// A chart of line and bar series with auto-scaled axes, a legend and a readout of the
+/// point closest to the mouse. Points can be appended one by one, each series keeping
+/// only its most recent points when it has a maximal number of points
+pub struct ChartPar {
+    widget_data: WidgetData,
+    series: RefCell<Vec<ChartSeries>>,
+    x_range: Cell<Option<(f32, f32)>>,
+    y_range: Cell<Option<(f32, f32)>>,
+    x_ticks: RefCell<AxisTicks>,
+    y_ticks: RefCell<AxisTicks>,
+    legend_height: Cell<f32>,
+    /// the area of the data, in the same coordinates as the content layout
+    plot_layout: Cell<Rectangle>,
+    /// the series and the index of the point under the mouse
+    hoovered_point: Cell<Option<(usize, usize)>>,
+}
+
+impl Deref for ChartPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl ChartPar {
+    pub fn new() -> Self {
+        let par = Self {
+            widget_data: WidgetData::new(),
+            series: RefCell::new(Vec::new()),
+            x_range: Cell::new(None),
+            y_range: Cell::new(None),
+            x_ticks: RefCell::new(AxisTicks::default()),
+            y_ticks: RefCell::new(AxisTicks::default()),
+            legend_height: Cell::new(0.0),
+            plot_layout: Cell::new(Rectangle::default()),
+            hoovered_point: Cell::new(None),
+        };
+        par.set_hooverable(true);
+        par
+    }
+
+    /// Add an empty series and return its index
+    pub fn add_series(&self, gui: &Gui, name: &str, kind: SeriesKind, color: Color) -> usize {
+        let mut series = self.series.borrow_mut();
+        series.push(ChartSeries {
+            name: name.to_string(),
+            kind,
+            color,
+            points: VecDeque::new(),
+            max_points: None,
+            name_size: Size::empty(),
+        });
+        self.invalidate_preferred_size(gui);
+        series.len() - 1
+    }
+
+    pub fn series_count(&self) -> usize {
+        self.series.borrow().len()
+    }
+
+    /// Keep only the `max_points` most recent points of the series, or all of them with `None`
+    pub fn set_max_points(&self, gui: &Gui, series: usize, max_points: Option<usize>) -> &ChartPar {
+        if let Some(series) = self.series.borrow_mut().get_mut(series) {
+            series.max_points = max_points;
+            Self::drop_old_points(series);
+        }
+        self.invalidate_position(gui);
+        self
+    }
+
+    /// Append a point to the series, dropping the oldest one if the series is full
+    pub fn push_point(&self, gui: &Gui, series: usize, x: f32, y: f32) -> &ChartPar {
+        if let Some(series) = self.series.borrow_mut().get_mut(series) {
+            series.points.push_back(Vector2::new(x, y));
+            Self::drop_old_points(series);
+        }
+        self.invalidate_position(gui);
+        self
+    }
+
+    /// Replace all the points of the series
+    pub fn set_points(&self, gui: &Gui, series: usize, points: &[(f32, f32)]) -> &ChartPar {
+        if let Some(series) = self.series.borrow_mut().get_mut(series) {
+            series.points = points.iter().map(|(x, y)| Vector2::new(*x, *y)).collect();
+            Self::drop_old_points(series);
+        }
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn clear_points(&self, gui: &Gui, series: usize) -> &ChartPar {
+        self.set_points(gui, series, &[])
+    }
+
+    /// Fix the range of the horizontal axis, or fit it to the data with `None`.
+    /// A reversed range is swapped, a range with a non-finite bound fits the axis to the data
+    pub fn set_x_range(&self, gui: &Gui, range: Option<(f32, f32)>) -> &ChartPar {
+        self.x_range.set(range.and_then(normalize_range));
+        self.invalidate_position(gui);
+        self
+    }
+
+    /// Fix the range of the vertical axis, or fit it to the data with `None`.
+    /// A reversed range is swapped, a range with a non-finite bound fits the axis to the data
+    pub fn set_y_range(&self, gui: &Gui, range: Option<(f32, f32)>) -> &ChartPar {
+        self.y_range.set(range.and_then(normalize_range));
+        self.invalidate_position(gui);
+        self
+    }
+
+    fn drop_old_points(series: &mut ChartSeries) {
+        if let Some(max_points) = series.max_points {
+            while series.points.len() > max_points {
+                series.points.pop_front();
+            }
+        }
+    }
+
+    /// The bounds of the data, zero being always included on the vertical axis with bars
+    fn data_bounds(&self) -> (f32, f32, f32, f32) {
+        let series = self.series.borrow();
+        let mut bounds = (f32::INFINITY, f32::NEG_INFINITY, f32::INFINITY, f32::NEG_INFINITY);
+        for item in series.iter() {
+            for point in item.points.iter().filter(|point| point.x.is_finite() && point.y.is_finite()) {
+                bounds.0 = bounds.0.min(point.x);
+                bounds.1 = bounds.1.max(point.x);
+                bounds.2 = bounds.2.min(point.y);
+                bounds.3 = bounds.3.max(point.y);
+            }
+            if item.kind == SeriesKind::Bar && !item.points.is_empty() {
+                bounds.2 = bounds.2.min(0.0);
+                bounds.3 = bounds.3.max(0.0);
+            }
+        }
+        if bounds.0 > bounds.1 {
+            (0.0, 1.0, 0.0, 1.0)
+        } else {
+            bounds
+        }
+    }
+
+    fn compute_axis_ticks(&self, min: f32, max: f32, fixed: bool) -> AxisTicks {
+        let (min, max) = if max - min > f32::EPSILON { (min, max) } else { (min - 0.5, max + 0.5) };
+        let step = nice_step((max - min) / CHART_TICK_TARGET);
+        // an auto-scaled axis is extended to the ticks around the data
+        let (min, max) = if fixed { (min, max) } else { ((min / step).floor() * step, (max / step).ceil() * step) };
+        let decimals = (-step.log10().floor()).max(0.0) as usize;
+
+        // the values are computed from their rank, adding the step to a large value may not change it
+        let first = (min / step).ceil() * step;
+        let nb_steps = ((max - first) / step + 1e-3).floor();
+        let values: Vec<f32> = if nb_steps.is_finite() && nb_steps >= 0.0 {
+            (0..=(nb_steps as usize).min(CHART_MAX_TICKS)).map(|i| first + i as f32 * step).collect()
+        } else {
+            Vec::new()
+        };
+
+        let labels = values.iter().map(|value| {
+            let label = format!("{:.*}", decimals, value);
+            let label_size = match self.text_style() {
+                None => Size::empty(),
+                Some(ts) => ts.measure_text(&label),
+            };
+            (label, label_size)
+        }).collect();
+
+        AxisTicks { min, max, values, labels }
+    }
+
+    fn to_screen(&self, point: &Vector2, plot_layout: &Rectangle) -> Vector2 {
+        let x_ticks = self.x_ticks.borrow();
+        let y_ticks = self.y_ticks.borrow();
+        Vector2::new(
+            plot_layout.x + (point.x - x_ticks.min) / (x_ticks.max - x_ticks.min) * plot_layout.width,
+            plot_layout.y + plot_layout.height - (point.y - y_ticks.min) / (y_ticks.max - y_ticks.min) * plot_layout.height,
+        )
+    }
+
+    /// The width of a bar and the offset of the bar of each bar series from the x of its point
+    fn bar_geometry(&self, plot_layout: &Rectangle) -> (f32, Vec<f32>) {
+        let series = self.series.borrow();
+        let x_ticks = self.x_ticks.borrow();
+        let bar_series: Vec<usize> = (0..series.len()).filter(|index| series[*index].kind == SeriesKind::Bar).collect();
+
+        let mut min_spacing = f32::INFINITY;
+        for index in bar_series.iter() {
+            let points = &series[*index].points;
+            for (previous, next) in points.iter().zip(points.iter().skip(1)) {
+                let spacing = (next.x - previous.x).abs();
+                if spacing > 0.0 {
+                    min_spacing = min_spacing.min(spacing);
+                }
+            }
+        }
+        let slot_width = if min_spacing.is_finite() {
+            min_spacing / (x_ticks.max - x_ticks.min) * plot_layout.width
+        } else {
+            plot_layout.width / CHART_TICK_TARGET
+        };
+        let bar_width = slot_width * CHART_BAR_RATIO / bar_series.len().max(1) as f32;
+
+        let mut offsets = vec![0.0; series.len()];
+        for (position, index) in bar_series.iter().enumerate() {
+            offsets[*index] = (position as f32 - bar_series.len() as f32 * 0.5) * bar_width;
+        }
+        (bar_width, offsets)
+    }
+
+    fn render_legend(&self, d: &mut impl RaylibDraw, content_layout: &Rectangle) {
+        let text_style = match self.text_style() {
+            None => return,
+            Some(ts) => ts,
+        };
+        let mut x = content_layout.x;
+        let y = content_layout.y;
+        let legend_height = self.legend_height.get();
+        for item in self.series.borrow().iter() {
+            let swatch_y = y + (legend_height - CHART_LEGEND_SWATCH_SIZE) * 0.5;
+            d.draw_rectangle_rec(Rectangle::new(x, swatch_y, CHART_LEGEND_SWATCH_SIZE, CHART_LEGEND_SWATCH_SIZE), item.color);
+            x += CHART_LEGEND_SWATCH_SIZE + CHART_LABEL_SPACING;
+            text_style.draw_text(d, &item.name, &Vector2::new(x, y + (legend_height - item.name_size.height()) * 0.5));
+            x += item.name_size.width() + CHART_LEGEND_SPACING;
+        }
+    }
+
+    fn render_axes(&self, d: &mut impl RaylibDraw, plot_layout: &Rectangle) {
+        let text_style = self.text_style();
+        let bottom = plot_layout.y + plot_layout.height;
+
+        let x_ticks = self.x_ticks.borrow();
+        for (value, (label, label_size)) in x_ticks.values.iter().zip(x_ticks.labels.iter()) {
+            let x = plot_layout.x + (value - x_ticks.min) / (x_ticks.max - x_ticks.min) * plot_layout.width;
+            d.draw_line_v(Vector2::new(x, plot_layout.y), Vector2::new(x, bottom), CHART_GRID_COLOR);
+            d.draw_line_v(Vector2::new(x, bottom), Vector2::new(x, bottom + CHART_TICK_LENGTH), CHART_AXIS_COLOR);
+            if let Some(ts) = &text_style {
+                ts.draw_text(d, label, &Vector2::new(x - label_size.width() * 0.5, bottom + CHART_TICK_LENGTH + CHART_LABEL_SPACING));
+            }
+        }
+
+        let y_ticks = self.y_ticks.borrow();
+        for (value, (label, label_size)) in y_ticks.values.iter().zip(y_ticks.labels.iter()) {
+            let y = bottom - (value - y_ticks.min) / (y_ticks.max - y_ticks.min) * plot_layout.height;
+            d.draw_line_v(Vector2::new(plot_layout.x, y), Vector2::new(plot_layout.x + plot_layout.width, y), CHART_GRID_COLOR);
+            d.draw_line_v(Vector2::new(plot_layout.x - CHART_TICK_LENGTH, y), Vector2::new(plot_layout.x, y), CHART_AXIS_COLOR);
+            if let Some(ts) = &text_style {
+                let position = Vector2::new(
+                    plot_layout.x - CHART_TICK_LENGTH - CHART_LABEL_SPACING - label_size.width(),
+                    y - label_size.height() * 0.5,
+                );
+                ts.draw_text(d, label, &position);
+            }
+        }
+
+        d.draw_line_v(Vector2::new(plot_layout.x, plot_layout.y), Vector2::new(plot_layout.x, bottom), CHART_AXIS_COLOR);
+        d.draw_line_v(Vector2::new(plot_layout.x, bottom), Vector2::new(plot_layout.x + plot_layout.width, bottom), CHART_AXIS_COLOR);
+    }
+
+    fn render_series(&self, d: &mut impl RaylibDraw, plot_layout: &Rectangle) {
+        let (bar_width, bar_offsets) = self.bar_geometry(plot_layout);
+        let zero = self.to_screen(&Vector2::zero(), plot_layout).y.clamp(plot_layout.y, plot_layout.y + plot_layout.height);
+
+        for (index, item) in self.series.borrow().iter().enumerate() {
+            match item.kind {
+                SeriesKind::Line => {
+                    let mut previous: Option<Vector2> = None;
+                    for point in item.points.iter() {
+                        let position = self.to_screen(point, plot_layout);
+                        if let Some(previous) = previous {
+                            d.draw_line_v(previous, position, item.color);
+                        }
+                        previous = Some(position);
+                    }
+                }
+                SeriesKind::Bar => {
+                    for point in item.points.iter() {
+                        let position = self.to_screen(point, plot_layout);
+                        let x = position.x + bar_offsets[index];
+                        let (top, height) = if position.y < zero { (position.y, zero - position.y) } else { (zero, position.y - zero) };
+                        d.draw_rectangle_rec(Rectangle::new(x, top, bar_width, height), item.color);
+                    }
+                }
+            }
+        }
+    }
+
+    fn render_readout(&self, d: &mut impl RaylibDraw, plot_layout: &Rectangle, content_layout: &Rectangle) {
+        let (series_index, point_index) = match self.hoovered_point.get() {
+            None => return,
+            Some(hoovered_point) => hoovered_point,
+        };
+        let series = self.series.borrow();
+        let item = match series.get(series_index) {
+            None => return,
+            Some(item) => item,
+        };
+        let point = match item.points.get(point_index) {
+            None => return,
+            Some(point) => point,
+        };
+
+        let position = self.to_screen(point, plot_layout);
+        d.draw_circle_v(position, CHART_HOOVER_RADIUS, item.color);
+
+        if let Some(ts) = self.text_style() {
+            let decimals = |ticks: &AxisTicks| {
+                let step = ticks.values.get(1).zip(ticks.values.first()).map_or(1.0, |(second, first)| second - first);
+                (1.0 - step.log10().floor()).max(0.0) as usize
+            };
+            let text = format!(
+                "{}: ({:.*}, {:.*})",
+                item.name,
+                decimals(&self.x_ticks.borrow()), point.x,
+                decimals(&self.y_ticks.borrow()), point.y,
+            );
+            let text_size = ts.measure_text(&text);
+            let width = text_size.width() + 2.0 * CHART_READOUT_MARGIN;
+            let height = text_size.height() + 2.0 * CHART_READOUT_MARGIN;
+            // keep the readout inside the chart
+            let x = (position.x + CHART_HOOVER_RADIUS * 2.0).min(content_layout.x + content_layout.width - width).max(content_layout.x);
+            let y = (position.y - height - CHART_HOOVER_RADIUS * 2.0).max(content_layout.y);
+            let readout_layout = Rectangle::new(x, y, width, height);
+            d.draw_rectangle_rec(readout_layout, CHART_READOUT_COLOR);
+            d.draw_rectangle_lines_ex(readout_layout, 1, CHART_AXIS_COLOR);
+            ts.draw_text(d, &text, &Vector2::new(x + CHART_READOUT_MARGIN, y + CHART_READOUT_MARGIN));
+        }
+    }
+}
+
+impl WidgetSpecific for ChartPar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, _gui: &Gui) -> Size {
+        let text_style = self.text_style();
+        let mut legend_height = 0.0f32;
+        for item in self.series.borrow_mut().iter_mut() {
+            item.name_size = match &text_style {
+                None => Size::empty(),
+                Some(ts) => ts.measure_text(&item.name),
+            };
+            legend_height = legend_height.max(item.name_size.height()).max(CHART_LEGEND_SWATCH_SIZE);
+        }
+        self.legend_height.set(legend_height);
+
+        let size = Size::new(CHART_DEFAULT_WIDTH, CHART_DEFAULT_HEIGHT);
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&size.with_padding(&self.padding()));
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {
+        // the size of the chart does not depend on the data, so the ticks are computed with
+        // the layout of the plot, which is all a change of the data invalidates
+        let (x_min, x_max, y_min, y_max) = self.data_bounds();
+        let x_ticks = match self.x_range.get() {
+            None => self.compute_axis_ticks(x_min, x_max, false),
+            Some((min, max)) => self.compute_axis_ticks(min, max, true),
+        };
+        let y_ticks = match self.y_range.get() {
+            None => self.compute_axis_ticks(y_min, y_max, false),
+            Some((min, max)) => self.compute_axis_ticks(min, max, true),
+        };
+        self.x_ticks.replace(x_ticks);
+        self.y_ticks.replace(y_ticks);
+
+        let content_layout = self.content_layout();
+        let x_ticks = self.x_ticks.borrow();
+        let y_ticks = self.y_ticks.borrow();
+
+        let label_width = y_ticks.labels.iter().fold(0.0f32, |width, (_, size)| width.max(size.width()));
+        let label_height = x_ticks.labels.iter().fold(0.0f32, |height, (_, size)| height.max(size.height()));
+        // half a label is kept on the right and on the top for the labels of the last ticks
+        let last_x_label_width = x_ticks.labels.last().map_or(0.0, |(_, size)| size.width());
+        let top_y_label_height = y_ticks.labels.last().map_or(0.0, |(_, size)| size.height());
+
+        let legend_height = self.legend_height.get();
+        let legend_band = if legend_height > 0.0 { legend_height + CHART_LEGEND_SPACING } else { 0.0 };
+        let left = label_width + CHART_TICK_LENGTH + CHART_LABEL_SPACING;
+        let top = legend_band + top_y_label_height * 0.5;
+        let bottom = label_height + CHART_TICK_LENGTH + CHART_LABEL_SPACING;
+        let right = last_x_label_width * 0.5;
+
+        self.plot_layout.set(Rectangle::new(
+            content_layout.x + left,
+            content_layout.y + top,
+            (content_layout.width - left - right).max(0.0),
+            (content_layout.height - top - bottom).max(0.0),
+        ));
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+
+        let mut hoovered_point = None;
+        if self.get_hoover_state() {
+            let mouse_position = mouse_state.mouse_position();
+            let mut plot_layout = self.plot_layout.get();
+            plot_layout.x += offset.x;
+            plot_layout.y += offset.y;
+            let (_, bar_offsets) = self.bar_geometry(&plot_layout);
+
+            let mut best_distance = CHART_HOOVER_DISTANCE;
+            for (series_index, item) in self.series.borrow().iter().enumerate() {
+                for (point_index, point) in item.points.iter().enumerate() {
+                    let mut position = self.to_screen(point, &plot_layout);
+                    if item.kind == SeriesKind::Bar {
+                        position.x += bar_offsets[series_index];
+                    }
+                    let distance = position.distance_to(*mouse_position);
+                    if distance < best_distance {
+                        best_distance = distance;
+                        hoovered_point = Some((series_index, point_index));
+                    }
+                }
+            }
+        }
+        self.hoovered_point.set(hoovered_point);
+    }
+
+    fn render_my_visual(&self, gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let mut content_layout = self.content_layout();
+        content_layout.x += offset.x;
+        content_layout.y += offset.y;
+        let mut plot_layout = self.plot_layout.get();
+        plot_layout.x += offset.x;
+        plot_layout.y += offset.y;
+
+        self.render_legend(d, &content_layout);
+        self.render_axes(d, &plot_layout);
+        gui.push_clip(&plot_layout);
+        self.render_series(d, &plot_layout);
+        gui.pop_clip();
+        self.render_readout(d, &plot_layout, &content_layout);
+    }
+}
+
+/// Order the bounds of a fixed range, none when a bound is not finite
+fn normalize_range((min, max): (f32, f32)) -> Option<(f32, f32)> {
+    if !min.is_finite() || !max.is_finite() {
+        return None;
+    }
+    Some((min.min(max), min.max(max)))
+}
+
+/// Round a step up to 1, 2 or 5 times a power of ten
+fn nice_step(raw_step: f32) -> f32 {
+    if raw_step <= 0.0 || !raw_step.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10f32.powf(raw_step.log10().floor());
+    let normalized = raw_step / magnitude;
+    let nice = if normalized <= 1.0 {
+        1.0
+    } else if normalized <= 2.0 {
+        2.0
+    } else if normalized <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
@@ -0,0 +1,81 @@
+use raylib::prelude::*;
+
+/// Parse a color written as a name (`red`, `skyblue`...), as `#RRGGBB` or `#RRGGBBAA`
+/// (the `#` is optional) or as `r,g,b[,a]` with decimal components
+pub fn parse_color(text: &str) -> Option<Color> {
+    let text = text.trim();
+    if let Some(color) = named_color(&text.to_ascii_lowercase()) {
+        return Some(color);
+    }
+
+    if text.contains(',') {
+        let components: Vec<u8> = text.split(',')
+            .map(|component| component.trim().parse::<u8>())
+            .collect::<Result<_, _>>()
+            .ok()?;
+        return match components.as_slice() {
+            [r, g, b] => Some(Color::new(*r, *g, *b, 255)),
+            [r, g, b, a] => Some(Color::new(*r, *g, *b, *a)),
+            _ => None,
+        };
+    }
+
+    let hex = text.strip_prefix('#').unwrap_or(text);
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let component = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    let alpha = if hex.len() == 8 { component(6)? } else { 255 };
+    Some(Color::new(component(0)?, component(2)?, component(4)?, alpha))
+}
+
+fn named_color(name: &str) -> Option<Color> {
+    let color = match name {
+        "black" => Color::BLACK,
+        "white" => Color::WHITE,
+        "gray" => Color::GRAY,
+        "darkgray" => Color::DARKGRAY,
+        "red" => Color::RED,
+        "maroon" => Color::MAROON,
+        "orange" => Color::ORANGE,
+        "yellow" => Color::YELLOW,
+        "gold" => Color::GOLD,
+        "green" => Color::GREEN,
+        "darkgreen" => Color::DARKGREEN,
+        "lime" => Color::LIME,
+        "blue" => Color::BLUE,
+        "darkblue" => Color::DARKBLUE,
+        "skyblue" => Color::SKYBLUE,
+        "purple" => Color::PURPLE,
+        "violet" => Color::VIOLET,
+        "pink" => Color::PINK,
+        "brown" => Color::BROWN,
+        _ => return None,
+    };
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use raylib::prelude::Color;
+
+    use super::parse_color;
+
+    #[test]
+    fn parses_names_hex_and_components() {
+        assert_eq!(parse_color("SkyBlue"), Some(Color::SKYBLUE));
+        assert_eq!(parse_color("#FF8000"), Some(Color::new(255, 128, 0, 255)));
+        assert_eq!(parse_color("ff800080"), Some(Color::new(255, 128, 0, 128)));
+        assert_eq!(parse_color(" 255, 128,0 "), Some(Color::new(255, 128, 0, 255)));
+        assert_eq!(parse_color("255,128,0,128"), Some(Color::new(255, 128, 0, 128)));
+    }
+
+    #[test]
+    fn rejects_malformed_colors() {
+        assert_eq!(parse_color("nope"), None);
+        assert_eq!(parse_color("#FF80"), None);
+        assert_eq!(parse_color("#GG8000"), None);
+        assert_eq!(parse_color("256,0,0"), None);
+        assert_eq!(parse_color("1,2"), None);
+    }
+}
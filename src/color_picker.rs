@@ -0,0 +1,398 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+
+use raylib::prelude::*;
+
+use crate::color::parse_color;
+use crate::event::ColorPar;
+use crate::event::Event::ColorPicked;
+use crate::gui::Gui;
+use crate::keyboard::KeyboardState;
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+const COLOR_PICKER_SQUARE_SIZE: f32 = 150.0;
+const COLOR_PICKER_STRIP_THICKNESS: f32 = 20.0;
+const COLOR_PICKER_SPACING: f32 = 6.0;
+const COLOR_PICKER_ROW_HEIGHT: f32 = 24.0;
+const COLOR_PICKER_SWATCH_WIDTH: f32 = 40.0;
+const COLOR_PICKER_ENTRY_MARGIN: f32 = 4.0;
+const COLOR_PICKER_CHECKER_SIZE: f32 = 5.0;
+const COLOR_PICKER_BORDER_COLOR: Color = Color::DARKGRAY;
+const COLOR_PICKER_ENTRY_COLOR: Color = Color::RAYWHITE;
+const COLOR_PICKER_EDITING_COLOR: Color = Color::WHITE;
+const COLOR_PICKER_MARKER_COLOR: Color = Color::WHITE;
+
+/// The hue colors at the boundaries of the six segments of the hue strip
+const HUE_COLORS: [Color; 7] = [
+    Color::new(255, 0, 0, 255),
+    Color::new(255, 255, 0, 255),
+    Color::new(0, 255, 0, 255),
+    Color::new(0, 255, 255, 255),
+    Color::new(0, 0, 255, 255),
+    Color::new(255, 0, 255, 255),
+    Color::new(255, 0, 0, 255),
+];
+
+/// The part of the picker being dragged
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum PickerPart {
+    /// saturation horizontally and value vertically
+    Square,
+    Hue,
+    Alpha,
+}
+
+/// A color input made of a saturation/value square, a hue strip, an alpha strip,
+/// a swatch showing the picked color next to the previous one and a text entry
+/// accepting the colors of `color::parse_color`, like `#RRGGBB`, `r,g,b[,a]` or `red`.
+/// Dragging emits `ColorPicked` events like the slider emits `Drag` events : in
+/// progress while dragging, done on release and cancelled (restoring the previous
+/// color) on a right click.
+pub struct ColorPickerPar {
+    widget_data: WidgetData,
+    /// the committed color, the one before the drag in progress
+    color: Cell<Color>,
+    /// hue (0..360), saturation, value and alpha (0..1) of the displayed color
+    hsva: Cell<(f32, f32, f32, f32)>,
+    dragged_part: Cell<Option<PickerPart>>,
+    editing: Cell<bool>,
+    entry_text: RefCell<String>,
+    entry_text_size: Cell<Size>,
+    square_layout: Cell<Rectangle>,
+    hue_layout: Cell<Rectangle>,
+    alpha_layout: Cell<Rectangle>,
+    swatch_layout: Cell<Rectangle>,
+    entry_layout: Cell<Rectangle>,
+}
+
+impl Deref for ColorPickerPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl ColorPickerPar {
+    pub fn new() -> Self {
+        let par = Self {
+            widget_data: WidgetData::new(),
+            color: Cell::new(Color::WHITE),
+            hsva: Cell::new(to_hsva(Color::WHITE)),
+            dragged_part: Cell::new(None),
+            editing: Cell::new(false),
+            entry_text: RefCell::new(to_hex(Color::WHITE)),
+            entry_text_size: Cell::new(Size::empty()),
+            square_layout: Cell::new(Rectangle::default()),
+            hue_layout: Cell::new(Rectangle::default()),
+            alpha_layout: Cell::new(Rectangle::default()),
+            swatch_layout: Cell::new(Rectangle::default()),
+            entry_layout: Cell::new(Rectangle::default()),
+        };
+        par.set_focusable(true);
+        par
+    }
+
+    pub fn get_color(&self) -> Color {
+        self.color.get()
+    }
+
+    pub fn set_color(&self, gui: &Gui, color: Color) -> &ColorPickerPar {
+        self.color.set(color);
+        self.hsva.set(to_hsva(color));
+        self.dragged_part.set(None);
+        self.editing.set(false);
+        self.entry_text.replace(to_hex(color));
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// The color currently displayed, different from the committed one during a drag
+    fn effective_color(&self) -> Color {
+        from_hsva(self.hsva.get())
+    }
+
+    fn abs_layout(layout: &Cell<Rectangle>, offset: &Vector2) -> Rectangle {
+        let mut layout = layout.get();
+        layout.x += offset.x;
+        layout.y += offset.y;
+        layout
+    }
+
+    fn part_at(&self, offset: &Vector2, point: &Vector2) -> Option<PickerPart> {
+        if Self::abs_layout(&self.square_layout, offset).check_collision_point_rec(point) {
+            Some(PickerPart::Square)
+        } else if Self::abs_layout(&self.hue_layout, offset).check_collision_point_rec(point) {
+            Some(PickerPart::Hue)
+        } else if Self::abs_layout(&self.alpha_layout, offset).check_collision_point_rec(point) {
+            Some(PickerPart::Alpha)
+        } else {
+            None
+        }
+    }
+
+    /// Update the displayed color from the mouse position over the dragged part.
+    /// Returns true if it changed
+    fn drag_to(&self, part: PickerPart, offset: &Vector2, point: &Vector2) -> bool {
+        let (mut hue, mut saturation, mut value, mut alpha) = self.hsva.get();
+        match part {
+            PickerPart::Square => {
+                let layout = Self::abs_layout(&self.square_layout, offset);
+                saturation = ((point.x - layout.x) / layout.width).clamp(0.0, 1.0);
+                value = 1.0 - ((point.y - layout.y) / layout.height).clamp(0.0, 1.0);
+            }
+            PickerPart::Hue => {
+                let layout = Self::abs_layout(&self.hue_layout, offset);
+                hue = ((point.y - layout.y) / layout.height).clamp(0.0, 1.0) * 360.0;
+            }
+            PickerPart::Alpha => {
+                let layout = Self::abs_layout(&self.alpha_layout, offset);
+                alpha = ((point.x - layout.x) / layout.width).clamp(0.0, 1.0);
+            }
+        }
+        let hsva = (hue, saturation, value, alpha);
+        if hsva == self.hsva.get() {
+            return false;
+        }
+        self.hsva.set(hsva);
+        true
+    }
+
+    fn fire_color_event(&self, gui: &Gui, event: fn(String, Color) -> ColorPar, color: Color) {
+        if let Some(action_id) = self.action_id() {
+            gui.add_event(ColorPicked(event(action_id, color)))
+        }
+    }
+
+    fn stop_editing(&self, gui: &Gui) {
+        self.editing.set(false);
+        self.entry_text.replace(to_hex(self.effective_color()));
+        self.invalidate_preferred_size(gui);
+    }
+}
+
+impl WidgetSpecific for ColorPickerPar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, _gui: &Gui) -> Size {
+        let text_size = match self.text_style() {
+            None => Size::empty(),
+            Some(ts) => ts.measure_text(&self.entry_text.borrow()),
+        };
+        self.entry_text_size.set(text_size);
+
+        let row_height = COLOR_PICKER_ROW_HEIGHT.max(text_size.height() + 2.0 * COLOR_PICKER_ENTRY_MARGIN);
+        let width = COLOR_PICKER_SQUARE_SIZE + COLOR_PICKER_SPACING + COLOR_PICKER_STRIP_THICKNESS;
+        let height = COLOR_PICKER_SQUARE_SIZE + COLOR_PICKER_STRIP_THICKNESS + row_height + 2.0 * COLOR_PICKER_SPACING;
+
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&Size::new(width, height).with_padding(&self.padding()));
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {
+        let content_layout = self.content_layout();
+        let x = content_layout.x;
+        let y = content_layout.y;
+        let width = content_layout.width;
+
+        // the square takes the space left by the strips and the entry row
+        let row_height = COLOR_PICKER_ROW_HEIGHT.max(self.entry_text_size.get().height() + 2.0 * COLOR_PICKER_ENTRY_MARGIN);
+        let square_width = (width - COLOR_PICKER_SPACING - COLOR_PICKER_STRIP_THICKNESS).max(0.0);
+        let square_height = (content_layout.height - COLOR_PICKER_STRIP_THICKNESS - row_height - 2.0 * COLOR_PICKER_SPACING).max(0.0);
+
+        self.square_layout.set(Rectangle::new(x, y, square_width, square_height));
+        self.hue_layout.set(Rectangle::new(x + square_width + COLOR_PICKER_SPACING, y, COLOR_PICKER_STRIP_THICKNESS, square_height));
+
+        let alpha_y = y + square_height + COLOR_PICKER_SPACING;
+        self.alpha_layout.set(Rectangle::new(x, alpha_y, width, COLOR_PICKER_STRIP_THICKNESS));
+
+        let row_y = alpha_y + COLOR_PICKER_STRIP_THICKNESS + COLOR_PICKER_SPACING;
+        self.swatch_layout.set(Rectangle::new(x, row_y, COLOR_PICKER_SWATCH_WIDTH, row_height));
+        let entry_x = x + COLOR_PICKER_SWATCH_WIDTH + COLOR_PICKER_SPACING;
+        self.entry_layout.set(Rectangle::new(entry_x, row_y, (width - COLOR_PICKER_SWATCH_WIDTH - COLOR_PICKER_SPACING).max(0.0), row_height));
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+
+        if self.editing.get() && !self.has_focus(gui) {
+            self.stop_editing(gui);
+        }
+
+        let drag_info = mouse_state.drag_info();
+
+        if mouse_state.right().is_pressed() && self.dragged_part.get().is_some() {
+            let color = self.color.get();
+            self.dragged_part.set(None);
+            self.hsva.set(to_hsva(color));
+            self.entry_text.replace(to_hex(color));
+            self.invalidate_preferred_size(gui);
+            self.fire_color_event(gui, ColorPar::cancelled, color);
+            return;
+        }
+
+        if drag_info.started() {
+            let starting_position = drag_info.starting_position();
+            if let Some(part) = self.part_at(offset, starting_position) {
+                if self.editing.get() {
+                    self.stop_editing(gui);
+                }
+                self.dragged_part.set(Some(part));
+                if self.drag_to(part, offset, starting_position) {
+                    self.entry_text.replace(to_hex(self.effective_color()));
+                    self.invalidate_preferred_size(gui);
+                    self.fire_color_event(gui, ColorPar::in_progress, self.effective_color());
+                }
+            } else if Self::abs_layout(&self.entry_layout, offset).check_collision_point_rec(starting_position) {
+                self.editing.set(true);
+                self.entry_text.replace(to_hex(self.effective_color()));
+                self.invalidate_preferred_size(gui);
+            }
+        } else if drag_info.in_progress() {
+            if let Some(part) = self.dragged_part.get() {
+                if self.drag_to(part, offset, drag_info.current_position()) {
+                    self.entry_text.replace(to_hex(self.effective_color()));
+                    self.invalidate_preferred_size(gui);
+                    self.fire_color_event(gui, ColorPar::in_progress, self.effective_color());
+                }
+            }
+        } else if drag_info.done() && self.dragged_part.get().is_some() {
+            let color = self.effective_color();
+            self.dragged_part.set(None);
+            self.color.set(color);
+            self.fire_color_event(gui, ColorPar::done, color);
+        }
+    }
+
+    fn update_key_action(&self, gui: &Gui, keyboard_state: &KeyboardState) {
+        if !self.editing.get() {
+            return;
+        }
+
+        let mut changed = false;
+        for c in keyboard_state.typed_chars() {
+            if c.is_ascii_hexdigit() || *c == '#' || *c == ',' || *c == ' ' {
+                self.entry_text.borrow_mut().push(*c);
+                changed = true;
+            }
+        }
+        if keyboard_state.is_pressed(KeyboardKey::KEY_BACKSPACE) {
+            changed |= self.entry_text.borrow_mut().pop().is_some();
+        }
+        if changed {
+            self.invalidate_preferred_size(gui);
+        }
+
+        if keyboard_state.is_pressed(KeyboardKey::KEY_ENTER) || keyboard_state.is_pressed(KeyboardKey::KEY_KP_ENTER) {
+            let parsed = parse_color(&self.entry_text.borrow());
+            if let Some(color) = parsed {
+                self.color.set(color);
+                self.hsva.set(to_hsva(color));
+                self.fire_color_event(gui, ColorPar::done, color);
+            }
+            self.stop_editing(gui);
+        } else if keyboard_state.is_pressed(KeyboardKey::KEY_ESCAPE) {
+            self.stop_editing(gui);
+        }
+    }
+
+    fn render_my_visual(&self, _gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let (hue, saturation, value, alpha) = self.hsva.get();
+        let color = self.effective_color();
+
+        // saturation/value square : white to the pure hue horizontally, then transparent to black vertically
+        let square = Self::abs_layout(&self.square_layout, offset);
+        let hue_color = Color::color_from_hsv(hue, 1.0, 1.0);
+        d.draw_rectangle_gradient_h(square.x as i32, square.y as i32, square.width as i32, square.height as i32, Color::WHITE, hue_color);
+        d.draw_rectangle_gradient_v(square.x as i32, square.y as i32, square.width as i32, square.height as i32, Color::BLANK, Color::BLACK);
+        d.draw_rectangle_lines_ex(square, 1, COLOR_PICKER_BORDER_COLOR);
+        let marker = Vector2::new(square.x + saturation * square.width, square.y + (1.0 - value) * square.height);
+        d.draw_circle_lines(marker.x as i32, marker.y as i32, 4.0, COLOR_PICKER_MARKER_COLOR);
+        d.draw_circle_lines(marker.x as i32, marker.y as i32, 5.0, Color::BLACK);
+
+        // hue strip, in six gradients
+        let strip = Self::abs_layout(&self.hue_layout, offset);
+        let segment_height = strip.height / 6.0;
+        for segment in 0..6 {
+            let y = strip.y + segment as f32 * segment_height;
+            d.draw_rectangle_gradient_v(strip.x as i32, y as i32, strip.width as i32, segment_height.ceil() as i32, HUE_COLORS[segment], HUE_COLORS[segment + 1]);
+        }
+        d.draw_rectangle_lines_ex(strip, 1, COLOR_PICKER_BORDER_COLOR);
+        let hue_y = strip.y + hue / 360.0 * strip.height;
+        d.draw_rectangle_lines_ex(Rectangle::new(strip.x - 2.0, hue_y - 2.0, strip.width + 4.0, 4.0), 1, Color::BLACK);
+
+        // alpha strip over a checkerboard
+        let strip = Self::abs_layout(&self.alpha_layout, offset);
+        draw_checkerboard(d, &strip);
+        let opaque = Color::new(color.r, color.g, color.b, 255);
+        d.draw_rectangle_gradient_h(strip.x as i32, strip.y as i32, strip.width as i32, strip.height as i32, opaque.fade(0.0), opaque);
+        d.draw_rectangle_lines_ex(strip, 1, COLOR_PICKER_BORDER_COLOR);
+        let alpha_x = strip.x + alpha * strip.width;
+        d.draw_rectangle_lines_ex(Rectangle::new(alpha_x - 2.0, strip.y - 2.0, 4.0, strip.height + 4.0), 1, Color::BLACK);
+
+        // swatch : the committed color on the left, the displayed one on the right
+        let swatch = Self::abs_layout(&self.swatch_layout, offset);
+        draw_checkerboard(d, &swatch);
+        let half_width = swatch.width * 0.5;
+        d.draw_rectangle_rec(Rectangle::new(swatch.x, swatch.y, half_width, swatch.height), self.color.get());
+        d.draw_rectangle_rec(Rectangle::new(swatch.x + half_width, swatch.y, swatch.width - half_width, swatch.height), color);
+        d.draw_rectangle_lines_ex(swatch, 1, COLOR_PICKER_BORDER_COLOR);
+
+        // text entry
+        let entry = Self::abs_layout(&self.entry_layout, offset);
+        d.draw_rectangle_rec(entry, if self.editing.get() { COLOR_PICKER_EDITING_COLOR } else { COLOR_PICKER_ENTRY_COLOR });
+        d.draw_rectangle_lines_ex(entry, 1, COLOR_PICKER_BORDER_COLOR);
+        if let Some(ts) = self.text_style() {
+            let text_size = self.entry_text_size.get();
+            let position = Vector2::new(entry.x + COLOR_PICKER_ENTRY_MARGIN, entry.y + (entry.height - text_size.height()) * 0.5);
+            ts.draw_text(d, &self.entry_text.borrow(), &position);
+            if self.editing.get() {
+                let cursor_x = position.x + text_size.width() + 1.0;
+                d.draw_line_v(Vector2::new(cursor_x, position.y), Vector2::new(cursor_x, position.y + text_size.height()), *ts.color());
+            }
+        }
+    }
+}
+
+fn draw_checkerboard(d: &mut impl RaylibDraw, layout: &Rectangle) {
+    d.draw_rectangle_rec(*layout, Color::WHITE);
+    let columns = (layout.width / COLOR_PICKER_CHECKER_SIZE).ceil() as i32;
+    let rows = (layout.height / COLOR_PICKER_CHECKER_SIZE).ceil() as i32;
+    for row in 0..rows {
+        for column in (row % 2..columns).step_by(2) {
+            let x = layout.x + column as f32 * COLOR_PICKER_CHECKER_SIZE;
+            let y = layout.y + row as f32 * COLOR_PICKER_CHECKER_SIZE;
+            let width = COLOR_PICKER_CHECKER_SIZE.min(layout.x + layout.width - x);
+            let height = COLOR_PICKER_CHECKER_SIZE.min(layout.y + layout.height - y);
+            d.draw_rectangle_rec(Rectangle::new(x, y, width, height), Color::LIGHTGRAY);
+        }
+    }
+}
+
+fn to_hsva(color: Color) -> (f32, f32, f32, f32) {
+    let hsv = color.color_to_hsv();
+    (hsv.x, hsv.y, hsv.z, color.a as f32 / 255.0)
+}
+
+fn from_hsva(hsva: (f32, f32, f32, f32)) -> Color {
+    let (hue, saturation, value, alpha) = hsva;
+    let mut color = Color::color_from_hsv(hue, saturation, value);
+    color.a = (alpha * 255.0).round() as u8;
+    color
+}
+
+fn to_hex(color: Color) -> String {
+    format!("#{:02X}{:02X}{:02X}{:02X}", color.r, color.g, color.b, color.a)
+}
@@ -0,0 +1,95 @@
+use generational_arena::Index;
+
+use crate::alignment::HAlignment::Middle;
+use crate::alignment::VAlignment::Center;
+use crate::fill::Fill::Enabled;
+use crate::gui::Gui;
+use crate::hbox::HBoxPar;
+use crate::label::LabelPar;
+use crate::padding::Padding;
+use crate::pane::PanePar;
+use crate::position::Coordinate::Relative;
+use crate::vbox::VBoxPar;
+use crate::widget::Widget::{HBox, Label, Pane, VBox};
+
+const DIALOG_PADDING: f32 = 15.0;
+const DIALOG_SPACING: f32 = 15.0;
+const DIALOG_BUTTON_PADDING: f32 = 8.0;
+
+/// The button used to close a dialog
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum DialogChoice {
+    Ok,
+    Cancel,
+}
+
+impl DialogChoice {
+    fn label(&self) -> &'static str {
+        match self {
+            DialogChoice::Ok => "OK",
+            DialogChoice::Cancel => "Cancel",
+        }
+    }
+}
+
+/// A dialog shown in its own modal layer, waiting for the user choice
+pub(crate) struct DialogInfo {
+    pub(crate) dialog_id: String,
+    pub(crate) layer_root: Index,
+    /// the action ids of the buttons with the choice they stand for
+    pub(crate) buttons: Vec<(String, DialogChoice)>,
+    /// the choice made when the dialog is dismissed with the escape key
+    pub(crate) escape_choice: DialogChoice,
+}
+
+/// Build the widgets of a dialog in a new modal layer
+pub(crate) fn build_dialog(gui: &mut Gui, dialog_id: &str, title: &str, message: &str, choices: &[DialogChoice]) -> DialogInfo {
+    let layer_root = {
+        let par = PanePar::new();
+        par.enable_fill_width(gui, Enabled { weight: 1 })
+            .enable_fill_height(gui, Enabled { weight: 1 })
+            .set_background_style("none")
+            .set_border_style("none");
+        gui.add_layer(Pane(par), true)
+    };
+
+    let vbox = {
+        let par = VBoxPar::new();
+        par.set_spacing(gui, DIALOG_SPACING)
+            .set_padding(gui, Padding::same(DIALOG_PADDING))
+            .set_position(gui, &Relative(50.0), &Relative(50.0))
+            .set_valignment(gui, Center)
+            .set_halignment(gui, Middle);
+        gui.add_child(layer_root, VBox(par))
+    };
+
+    for text in [title, message].iter() {
+        let par = LabelPar::new();
+        par.set_text(gui, text);
+        gui.add_child(vbox, Label(par));
+    }
+
+    let hbox = {
+        let par = HBoxPar::new();
+        par.set_spacing(gui, DIALOG_SPACING)
+            .set_background_style("none")
+            .set_border_style("none");
+        gui.add_child(vbox, HBox(par))
+    };
+
+    let mut buttons = Vec::new();
+    for choice in choices {
+        let action_id = format!("dialog:{}:{}", dialog_id, choice.label());
+        let par = LabelPar::new();
+        par.set_text(gui, choice.label())
+            .set_padding(gui, Padding::same(DIALOG_BUTTON_PADDING))
+            .set_action_id(&action_id)
+            .set_clickable(true)
+            .set_hooverable(true);
+        gui.add_child(hbox, Label(par));
+        buttons.push((action_id, *choice));
+    }
+
+    let escape_choice = if choices.contains(&DialogChoice::Cancel) { DialogChoice::Cancel } else { DialogChoice::Ok };
+    DialogInfo { dialog_id: dialog_id.to_string(), layer_root, buttons, escape_choice }
+}
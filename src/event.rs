@@ -1,8 +1,24 @@
+use generational_arena::Index;
+use raylib::color::Color;
+
+use crate::table::SortOrder;
+use crate::dialog::DialogChoice;
+use crate::split_pane::SplitSide;
 
 #[derive(Debug, Clone)]
 pub enum Event {
     Click(ClickPar),
-    Drag(DragPar)
+    Drag(DragPar),
+    Select(SelectPar),
+    Sort(SortPar),
+    TabChanged(TabPar),
+    TabCloseRequested(TabPar),
+    MenuActivated(MenuPar),
+    DialogClosed(DialogPar),
+    SplitChanged(SplitPar),
+    ColorPicked(ColorPar),
+    RangeDrag(RangePar),
+    LinkClicked(LinkPar),
 }
 
 #[derive(Debug, Clone)]
@@ -58,4 +74,250 @@ impl DragPar {
     pub fn value(&self) -> f32 {
         self.value
     }
-}
\ No newline at end of file
+}
+
+
+#[derive(Debug, Clone)]
+pub struct SelectPar {
+    action_id:String,
+    selection:Vec<usize>,
+}
+
+impl SelectPar {
+    pub fn new(action_id:&String, selection:Vec<usize>) -> Self {
+        Self{action_id:action_id.to_owned(),selection}
+    }
+
+    pub fn action_id(&self) -> &str {
+        &self.action_id
+    }
+    /// the indices of the selected items, in increasing order
+    pub fn selection(&self) -> &[usize] {
+        &self.selection
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct SortPar {
+    action_id:String,
+    column:usize,
+    order:SortOrder,
+}
+
+impl SortPar {
+    pub fn new(action_id:&String, column:usize, order:SortOrder) -> Self {
+        Self{action_id:action_id.to_owned(),column,order}
+    }
+
+    pub fn action_id(&self) -> &str {
+        &self.action_id
+    }
+    pub fn column(&self) -> usize {
+        self.column
+    }
+    pub fn order(&self) -> SortOrder {
+        self.order
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct TabPar {
+    action_id:String,
+    tab:usize,
+    child:Index,
+}
+
+impl TabPar {
+    pub fn new(action_id:&String, tab:usize, child:Index) -> Self {
+        Self{action_id:action_id.to_owned(),tab,child}
+    }
+
+    pub fn action_id(&self) -> &str {
+        &self.action_id
+    }
+    /// the display position of the tab in the header
+    pub fn tab(&self) -> usize {
+        self.tab
+    }
+    /// the tree index of the child shown by the tab
+    pub fn child(&self) -> Index {
+        self.child
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct MenuPar {
+    item_id:String,
+    checked:bool,
+}
+
+impl MenuPar {
+    pub fn new(item_id:&String, checked:bool) -> Self {
+        Self{item_id:item_id.to_owned(),checked}
+    }
+
+    pub fn item_id(&self) -> &str {
+        &self.item_id
+    }
+    /// the state of a checkable item after its activation
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct DialogPar {
+    dialog_id:String,
+    choice:DialogChoice,
+}
+
+impl DialogPar {
+    pub fn new(dialog_id:&String, choice:DialogChoice) -> Self {
+        Self{dialog_id:dialog_id.to_owned(),choice}
+    }
+
+    pub fn dialog_id(&self) -> &str {
+        &self.dialog_id
+    }
+    pub fn choice(&self) -> DialogChoice {
+        self.choice
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct SplitPar {
+    action_id:String,
+    first_size:f32,
+    ratio:f32,
+    collapsed:Option<SplitSide>,
+    in_progress:bool,
+}
+
+impl SplitPar {
+    pub fn new(action_id:&String, first_size:f32, ratio:f32, collapsed:Option<SplitSide>, in_progress:bool) -> Self {
+        Self{action_id:action_id.to_owned(),first_size,ratio,collapsed,in_progress}
+    }
+
+    pub fn action_id(&self) -> &str {
+        &self.action_id
+    }
+    /// the size in pixels of the first side along the orientation of the split
+    pub fn first_size(&self) -> f32 {
+        self.first_size
+    }
+    /// the part of the space given to the first side, between 0 and 1
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+    pub fn collapsed(&self) -> Option<SplitSide> {
+        self.collapsed
+    }
+    /// true while the divider is dragged
+    pub fn is_in_progress(&self) -> bool {
+        self.in_progress
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct ColorPar {
+    action_id:String,
+    color:Color,
+    in_progress:bool,
+    cancelled:bool,
+}
+
+impl ColorPar {
+
+    pub fn in_progress(action_id:String,color:Color) ->Self {
+        Self{action_id,color, in_progress:true,cancelled:false}
+    }
+
+    pub fn cancelled(action_id:String,color:Color) ->Self {
+        Self{action_id,color, in_progress:false,cancelled:true}
+    }
+
+    pub fn done(action_id:String,color:Color) ->Self {
+        Self{action_id,color, in_progress:false,cancelled:false}
+    }
+
+    pub fn is_in_progress(&self) -> bool {
+        self.in_progress
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    pub fn action_id(&self) -> &str {
+        &self.action_id
+    }
+    /// the picked color, or the restored one when the drag is cancelled
+    pub fn color(&self) -> Color {
+        self.color
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct RangePar {
+    action_id:String,
+    low:f32,
+    high:f32,
+    in_progress:bool,
+    cancelled:bool,
+}
+
+impl RangePar {
+
+    pub fn in_progress(action_id:String,low:f32,high:f32) ->Self {
+        Self{action_id,low,high, in_progress:true,cancelled:false}
+    }
+
+    pub fn cancelled(action_id:String,low:f32,high:f32) ->Self {
+        Self{action_id,low,high, in_progress:false,cancelled:true}
+    }
+
+    pub fn done(action_id:String,low:f32,high:f32) ->Self {
+        Self{action_id,low,high, in_progress:false,cancelled:false}
+    }
+
+    pub fn is_in_progress(&self) -> bool {
+        self.in_progress
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    pub fn action_id(&self) -> &str {
+        &self.action_id
+    }
+    /// the lower bound of the range, the restored one when the drag is cancelled
+    pub fn low(&self) -> f32 {
+        self.low
+    }
+    /// the upper bound of the range, the restored one when the drag is cancelled
+    pub fn high(&self) -> f32 {
+        self.high
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct LinkPar {
+    link_id:String,
+}
+
+impl LinkPar {
+    pub fn new(link_id:&String) -> Self {
+        Self{link_id:link_id.to_owned()}
+    }
+
+    pub fn link_id(&self) -> &str {
+        &self.link_id
+    }
+}
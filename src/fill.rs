@@ -11,6 +11,39 @@ pub enum  Fill {
 impl Fill {
 }
 
+/// Share `space` between filling widgets according to their (weight, min, max) sizes,
+/// a max of zero meaning no max. The space a widget cannot take because of its max
+/// goes to the other ones, and the ones below their min get their min
+pub(crate) fn share_by_weight(space: f32, shares: &[(u32, f32, f32)]) -> Vec<f32> {
+    let mut sizes: Vec<Option<f32>> = vec![None; shares.len()];
+    loop {
+        let free_weight: u32 = shares.iter().zip(sizes.iter()).filter(|(_, size)| size.is_none()).map(|(share, _)| share.0).sum();
+        let taken: f32 = sizes.iter().flatten().sum();
+        let unit = (space - taken).max(0.0) / (free_weight.max(1) as f32);
+
+        let mut clamped = false;
+        for (index, (weight, _, max)) in shares.iter().enumerate() {
+            if sizes[index].is_none() && *max > 0.0 && unit * *weight as f32 > *max {
+                sizes[index] = Some(*max);
+                clamped = true;
+            }
+        }
+        if !clamped {
+            for (index, (weight, min, _)) in shares.iter().enumerate() {
+                if sizes[index].is_none() && (unit * *weight as f32) < *min {
+                    sizes[index] = Some(*min);
+                    clamped = true;
+                }
+            }
+        }
+        if !clamped {
+            return shares.iter().zip(sizes.iter())
+                .map(|((weight, _, _), size)| size.unwrap_or(unit * *weight as f32))
+                .collect();
+        }
+    }
+}
+
 impl Fill {
 
     pub fn is_disabled(&self) -> bool {
@@ -29,3 +62,44 @@ impl Fill {
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::share_by_weight;
+
+    #[test]
+    fn shares_by_weight_without_constraints() {
+        assert_eq!(share_by_weight(90.0, &[(1, 0.0, 0.0), (2, 0.0, 0.0)]), vec![30.0, 60.0]);
+    }
+
+    #[test]
+    fn gives_the_space_above_a_max_to_the_others() {
+        assert_eq!(share_by_weight(100.0, &[(1, 0.0, 20.0), (1, 0.0, 0.0)]), vec![20.0, 80.0]);
+    }
+
+    #[test]
+    fn stops_at_the_max_when_every_share_is_clamped() {
+        assert_eq!(share_by_weight(100.0, &[(1, 0.0, 10.0), (1, 0.0, 20.0)]), vec![10.0, 20.0]);
+    }
+
+    #[test]
+    fn takes_the_min_from_the_others() {
+        assert_eq!(share_by_weight(100.0, &[(1, 80.0, 0.0), (1, 0.0, 0.0)]), vec![80.0, 20.0]);
+    }
+
+    #[test]
+    fn keeps_the_mins_when_they_do_not_fit() {
+        assert_eq!(share_by_weight(100.0, &[(1, 60.0, 0.0), (1, 60.0, 0.0)]), vec![60.0, 60.0]);
+    }
+
+    #[test]
+    fn gives_nothing_to_a_zero_weight() {
+        assert_eq!(share_by_weight(100.0, &[(0, 0.0, 0.0), (1, 0.0, 0.0)]), vec![0.0, 100.0]);
+        assert_eq!(share_by_weight(100.0, &[(0, 0.0, 0.0), (0, 0.0, 0.0)]), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn shares_no_negative_space() {
+        assert_eq!(share_by_weight(-10.0, &[(1, 0.0, 0.0), (1, 0.0, 0.0)]), vec![0.0, 0.0]);
+    }
+}
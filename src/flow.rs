@@ -0,0 +1,216 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+
+use raylib::prelude::*;
+use generational_arena::Index;
+
+use crate::alignment::VAlignment;
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::{LayoutableWidget, WidgetSpecific};
+
+/// A container placing its children from left to right and starting a new line
+/// when the next child does not fit in the width left. Children filling the height
+/// take the height of their line, the other ones are aligned in it
+pub struct FlowPar {
+    widget_data: WidgetData,
+    item_spacing: Cell<f32>,
+    line_spacing: Cell<f32>,
+    line_alignment: Cell<VAlignment>,
+    /// the children of each line with the height of the line, for the current content width
+    lines: RefCell<Vec<(f32, Vec<Index>)>>,
+}
+
+impl Deref for FlowPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl FlowPar {
+    pub fn new() -> Self {
+        Self {
+            widget_data: WidgetData::new(),
+            item_spacing: Cell::new(10.0),
+            line_spacing: Cell::new(10.0),
+            line_alignment: Cell::new(VAlignment::Center),
+            lines: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// The horizontal space between two children of a line
+    pub fn set_item_spacing(&self, gui: &Gui, spacing: f32) -> &FlowPar {
+        if spacing.eq(&self.item_spacing.get()) {
+            return self;
+        }
+        self.item_spacing.set(spacing);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn get_item_spacing(&self) -> f32 {
+        self.item_spacing.get()
+    }
+
+    /// The vertical space between two lines
+    pub fn set_line_spacing(&self, gui: &Gui, spacing: f32) -> &FlowPar {
+        if spacing.eq(&self.line_spacing.get()) {
+            return self;
+        }
+        self.line_spacing.set(spacing);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn get_line_spacing(&self) -> f32 {
+        self.line_spacing.get()
+    }
+
+    /// How the children smaller than their line are aligned in it
+    pub fn set_line_alignment(&self, gui: &Gui, alignment: VAlignment) -> &FlowPar {
+        self.line_alignment.set(alignment);
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn get_line_alignment(&self) -> VAlignment {
+        self.line_alignment.get()
+    }
+
+    /// Break the children in lines fitting in `width`, each child with its size.
+    /// A line holds at least one child, even when it is wider than `width`
+    fn flow_lines(&self, gui: &Gui, tree_index: Index, width: f32) -> Vec<Vec<(Index, Size)>> {
+        let spacing = self.item_spacing.get();
+        let mut lines: Vec<Vec<(Index, Size)>> = Vec::new();
+        let mut line_width: f32 = 0.0;
+
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(child) = gui.get_widget(child_index) {
+                let child_width = child.compute_computed_size(gui).width().min(width);
+                let child_height = child.compute_height_for_width(gui, child_width);
+                let size = Size::new(child_width, child_height);
+
+                match lines.last_mut() {
+                    Some(line) if line_width + spacing + child_width <= width => {
+                        line_width += spacing + child_width;
+                        line.push((child_index, size));
+                    }
+                    _ => {
+                        line_width = child_width;
+                        lines.push(vec![(child_index, size)]);
+                    }
+                }
+            }
+        }
+        lines
+    }
+
+    /// The height of the lines, spacing included
+    fn lines_height(&self, lines: &[Vec<(Index, Size)>]) -> f32 {
+        let height: f32 = lines.iter()
+            .map(|line| line.iter().fold(0.0f32, |height, (_, size)| height.max(size.height())))
+            .sum();
+        height + self.line_spacing.get() * ((lines.len() as i32 - 1).max(0) as f32)
+    }
+}
+
+impl WidgetSpecific for FlowPar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    /// The preferred size puts all the children on a single line
+    fn compute_size(&self, gui: &Gui) -> Size {
+        let tree_index = match self.get_tree_index() {
+            None => return Size::empty(),
+            Some(tree_index) => tree_index,
+        };
+
+        let mut nb_children = 0;
+        let mut summed_width: f32 = 0.0;
+        let mut max_height: f32 = 0.0;
+
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(child) = gui.get_widget(child_index) {
+                let child_preferred_size = child.compute_computed_size(gui);
+                nb_children += 1;
+                summed_width += child_preferred_size.width();
+                max_height = max_height.max(child_preferred_size.height());
+            }
+        }
+        summed_width += self.item_spacing.get() * ((nb_children - 1).max(0) as f32);
+
+        let computed = Size::new(summed_width, max_height).with_padding(&self.padding());
+
+        let mut preferred = self.preferred_size();
+        preferred.replace_empty_dimensions_and_max(&computed);
+        preferred
+    }
+
+    fn compute_child_content_size(&self, gui: &Gui, available_size: Size) {
+        let tree_index = match self.get_tree_index() {
+            None => return,
+            Some(tree_index) => tree_index,
+        };
+
+        let padding = self.padding();
+        let width = available_size.width() - padding.h_padding();
+        if width < 0.0 {
+            return;
+        }
+
+        let flow_lines = self.flow_lines(gui, tree_index, width);
+        let mut lines = Vec::with_capacity(flow_lines.len());
+        for line in flow_lines {
+            let line_height = line.iter().fold(0.0f32, |height, (_, size)| height.max(size.height()));
+            for (child_index, size) in line.iter() {
+                if let Some(child) = gui.get_widget(*child_index) {
+                    child.update_content_size(gui, &Size::new(size.width(), line_height));
+                }
+            }
+            lines.push((line_height, line.into_iter().map(|(child_index, _)| child_index).collect()));
+        }
+        self.lines.replace(lines);
+    }
+
+    fn compute_child_positions(&self, gui: &Gui) {
+        let item_spacing = self.item_spacing.get();
+        let line_spacing = self.line_spacing.get();
+        let shift_factor = self.line_alignment.get().shift_factor();
+
+        let mut position = Vector2::new(0.0, 0.0);
+        for (line_height, children) in self.lines.borrow().iter() {
+            position.x = 0.0;
+            for child_index in children {
+                if let Some(w) = gui.get_widget(*child_index) {
+                    let target = Vector2::new(position.x, position.y - (line_height - w.widget_height()) * shift_factor);
+                    w.set_widget_target(&target);
+                    w.update_child_positions(gui);
+                    position.x += w.widget_width() + item_spacing;
+                }
+            }
+            position.y += line_height + line_spacing;
+        }
+    }
+
+    fn height_for_width(&self, gui: &Gui, width: f32) -> Option<f32> {
+        let tree_index = self.get_tree_index()?;
+        let padding = self.padding();
+        let lines = self.flow_lines(gui, tree_index, width - padding.h_padding());
+        Some(self.preferred_size().height().max(self.lines_height(&lines) + padding.v_padding()))
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, _gui: &Gui, _d: &mut impl RaylibDraw, _offset: &Vector2) {}
+}
@@ -28,6 +28,24 @@ impl FontInfo {
         Size::from_vector2(&size)
     }
 
+    /// The distance from the top of a line drawn with this font to its baseline,
+    /// used to align texts drawn with different fonts
+    pub fn baseline(&self) -> f32 {
+        let font = self.font.as_ref();
+        let base_size = font.base_size();
+        // the bottom of a capital letter sits on the baseline
+        let glyph = font.chars().iter().find(|glyph| glyph.value == 'H' as i32);
+        match glyph {
+            Some(glyph) if base_size > 0 => (glyph.offsetY + glyph.image.height) as f32 * self.size / base_size as f32,
+            _ => self.size * 0.8,
+        }
+    }
+
+    /// A copy of this font information drawing at another size
+    pub fn with_size(&self, size: f32) -> FontInfo {
+        Self{font:self.font.clone(),size}
+    }
+
     /// Draw the provided text with this font information
     /// # Arguments
     ///
@@ -0,0 +1,385 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+
+use raylib::prelude::*;
+use generational_arena::Index;
+
+use crate::alignment::Alignment;
+use crate::fill::Fill;
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::widget::Widget;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::{LayoutableWidget, WidgetSpecific};
+
+/// The size of a row or a column of a grid
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GridTrack {
+    /// a size independent of the content
+    Fixed(f32),
+    /// the size of the largest child of the track
+    Auto,
+    /// a share of the space left by the other tracks, like `Fill::Enabled`
+    Weighted { weight: u32 },
+}
+
+/// The place of a child in a grid : the first row and column it covers, how many
+/// rows and columns it spans and how it is aligned in that area when it does not fill it
+#[derive(Copy, Clone, PartialEq)]
+pub struct GridCell {
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+    alignment: Alignment,
+}
+
+impl GridCell {
+    pub fn new(row: usize, column: usize) -> Self {
+        Self { row, column, row_span: 1, column_span: 1, alignment: Alignment::new() }
+    }
+
+    pub fn with_span(mut self, row_span: usize, column_span: usize) -> Self {
+        self.row_span = row_span.max(1);
+        self.column_span = column_span.max(1);
+        self
+    }
+
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn row(&self) -> usize {
+        self.row
+    }
+    pub fn column(&self) -> usize {
+        self.column
+    }
+    pub fn row_span(&self) -> usize {
+        self.row_span
+    }
+    pub fn column_span(&self) -> usize {
+        self.column_span
+    }
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+}
+
+/// A container laying out its children in rows and columns. Children are placed with
+/// `set_cell`, the ones without a cell go in the first row and column. Rows and columns
+/// missing from the definitions are `GridTrack::Auto`
+pub struct GridPar {
+    widget_data: WidgetData,
+    rows: RefCell<Vec<GridTrack>>,
+    columns: RefCell<Vec<GridTrack>>,
+    row_gap: Cell<f32>,
+    column_gap: Cell<f32>,
+    cells: RefCell<Vec<(Index, GridCell)>>,
+    /// the start and size of each row and column, in the same coordinates as the content layout
+    row_layouts: RefCell<Vec<(f32, f32)>>,
+    column_layouts: RefCell<Vec<(f32, f32)>>,
+}
+
+impl Deref for GridPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl GridPar {
+    pub fn new() -> Self {
+        Self {
+            widget_data: WidgetData::new(),
+            rows: RefCell::new(Vec::new()),
+            columns: RefCell::new(Vec::new()),
+            row_gap: Cell::new(10.0),
+            column_gap: Cell::new(10.0),
+            cells: RefCell::new(Vec::new()),
+            row_layouts: RefCell::new(Vec::new()),
+            column_layouts: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn set_rows(&self, gui: &Gui, rows: Vec<GridTrack>) -> &GridPar {
+        self.rows.replace(rows);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn set_columns(&self, gui: &Gui, columns: Vec<GridTrack>) -> &GridPar {
+        self.columns.replace(columns);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn set_gaps(&self, gui: &Gui, row_gap: f32, column_gap: f32) -> &GridPar {
+        if row_gap.eq(&self.row_gap.get()) && column_gap.eq(&self.column_gap.get()) {
+            return self;
+        }
+        self.row_gap.set(row_gap);
+        self.column_gap.set(column_gap);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn get_row_gap(&self) -> f32 {
+        self.row_gap.get()
+    }
+
+    pub fn get_column_gap(&self) -> f32 {
+        self.column_gap.get()
+    }
+
+    pub fn set_cell(&self, gui: &Gui, child: Index, cell: GridCell) -> &GridPar {
+        {
+            let mut cells = self.cells.borrow_mut();
+            match cells.iter_mut().find(|(index, _)| *index == child) {
+                Some((_, current)) => *current = cell,
+                None => cells.push((child, cell)),
+            }
+        }
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn get_cell(&self, child: Index) -> Option<GridCell> {
+        self.cells.borrow().iter().find(|(index, _)| *index == child).map(|(_, cell)| *cell)
+    }
+
+    /// The children of the grid with their cells
+    fn placed_children(&self, gui: &Gui, tree_index: Index) -> Vec<(Index, GridCell)> {
+        let cells = self.cells.borrow();
+        gui.get_widget_children(tree_index)
+            .map(|child_index| {
+                let cell = cells.iter().find(|(index, _)| *index == child_index)
+                    .map(|(_, cell)| *cell)
+                    .unwrap_or_else(|| GridCell::new(0, 0));
+                (child_index, cell)
+            })
+            .collect()
+    }
+
+    /// The sizes of the columns, sharing `width` between the weighted ones when provided
+    fn column_sizes(&self, gui: &Gui, children: &[(Index, GridCell)], width: Option<f32>) -> Vec<f32> {
+        let columns = self.columns.borrow();
+        let nb_columns = children.iter().map(|(_, cell)| cell.column + cell.column_span).fold(columns.len(), usize::max);
+        let extents: Vec<(usize, usize, f32)> = children.iter()
+            .filter_map(|(child_index, cell)| {
+                let child = gui.get_widget(*child_index)?;
+                Some((cell.column, cell.column_span, child.compute_computed_size(gui).width()))
+            })
+            .collect();
+        track_sizes(&columns, nb_columns, self.column_gap.get(), &extents, width)
+    }
+
+    /// The sizes of the rows for the given column sizes, sharing `height` between the weighted ones when provided
+    fn row_sizes(&self, gui: &Gui, children: &[(Index, GridCell)], column_sizes: &[f32], height: Option<f32>) -> Vec<f32> {
+        let rows = self.rows.borrow();
+        let nb_rows = children.iter().map(|(_, cell)| cell.row + cell.row_span).fold(rows.len(), usize::max);
+        let column_gap = self.column_gap.get();
+        let extents: Vec<(usize, usize, f32)> = children.iter()
+            .filter_map(|(child_index, cell)| {
+                let child = gui.get_widget(*child_index)?;
+                let cell_width = span_size(column_sizes, cell.column, cell.column_span, column_gap);
+                Some((cell.row, cell.row_span, child.compute_height_for_width(gui, child_width(gui, child, cell_width))))
+            })
+            .collect();
+        track_sizes(&rows, nb_rows, self.row_gap.get(), &extents, height)
+    }
+}
+
+/// The width a child gets from its cell, which decides its height when it depends on its width
+fn child_width(gui: &Gui, child: &Widget, cell_width: f32) -> f32 {
+    match child.fill_width() {
+        Fill::Enabled { .. } => cell_width,
+        Fill::Disabled => child.compute_computed_size(gui).width().min(cell_width),
+    }
+}
+
+/// The size covered by `span` tracks starting at `start`, gaps included
+fn span_size(sizes: &[f32], start: usize, span: usize, gap: f32) -> f32 {
+    let end = (start + span).min(sizes.len());
+    if start >= end {
+        return 0.0;
+    }
+    sizes[start..end].iter().sum::<f32>() + gap * (end - start - 1) as f32
+}
+
+/// The start and size of each track, laid out one after the other
+fn track_layouts(sizes: &[f32], gap: f32) -> Vec<(f32, f32)> {
+    let mut start = 0.0;
+    sizes.iter().map(|size| {
+        let layout = (start, *size);
+        start += size + gap;
+        layout
+    }).collect()
+}
+
+/// The sizes of `nb_tracks` tracks holding children of the given extents (first track, span, size).
+/// Auto tracks grow to their content, and the weighted ones too when there is no `available`
+/// space to share between them. A child spanning several tracks grows the ones sized by
+/// their content when they are too small for it
+fn track_sizes(tracks: &[GridTrack], nb_tracks: usize, gap: f32, extents: &[(usize, usize, f32)], available: Option<f32>) -> Vec<f32> {
+    let track = |index: usize| tracks.get(index).copied().unwrap_or(GridTrack::Auto);
+    let content_sized = |index: usize| match track(index) {
+        GridTrack::Fixed(_) => false,
+        GridTrack::Auto => true,
+        GridTrack::Weighted { .. } => available.is_none(),
+    };
+
+    let mut sizes: Vec<f32> = (0..nb_tracks).map(|index| match track(index) {
+        GridTrack::Fixed(size) => size,
+        _ => 0.0,
+    }).collect();
+
+    for (start, _, extent) in extents.iter().filter(|(_, span, _)| *span == 1) {
+        if content_sized(*start) {
+            sizes[*start] = sizes[*start].max(*extent);
+        }
+    }
+
+    for (start, span, extent) in extents.iter().filter(|(_, span, _)| *span > 1) {
+        let missing = extent - span_size(&sizes, *start, *span, gap);
+        let growing: Vec<usize> = (*start..(*start + *span).min(nb_tracks)).filter(|index| content_sized(*index)).collect();
+        if missing > 0.0 && !growing.is_empty() {
+            let growth = missing / growing.len() as f32;
+            for index in growing {
+                sizes[index] += growth;
+            }
+        }
+    }
+
+    if let Some(available) = available {
+        let summed_weight: u32 = (0..nb_tracks).map(|index| match track(index) {
+            GridTrack::Weighted { weight } => weight,
+            _ => 0,
+        }).sum();
+        let used = span_size(&sizes, 0, nb_tracks, gap);
+        let weighted_size = (available - used).max(0.0) / (summed_weight.max(1) as f32);
+        for (index, size) in sizes.iter_mut().enumerate() {
+            if let GridTrack::Weighted { weight } = track(index) {
+                *size = weighted_size * weight as f32;
+            }
+        }
+    }
+
+    sizes
+}
+
+impl WidgetSpecific for GridPar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, gui: &Gui) -> Size {
+        let tree_index = match self.get_tree_index() {
+            None => return Size::empty(),
+            Some(tree_index) => tree_index,
+        };
+
+        let children = self.placed_children(gui, tree_index);
+        let column_sizes = self.column_sizes(gui, &children, None);
+        let row_sizes = self.row_sizes(gui, &children, &column_sizes, None);
+
+        let computed = Size::new(
+            span_size(&column_sizes, 0, column_sizes.len(), self.column_gap.get()),
+            span_size(&row_sizes, 0, row_sizes.len(), self.row_gap.get()),
+        ).with_padding(&self.padding());
+
+        let mut preferred = self.preferred_size();
+        preferred.replace_empty_dimensions_and_max(&computed);
+        preferred
+    }
+
+    fn compute_child_content_size(&self, gui: &Gui, available_size: Size) {
+        let tree_index = match self.get_tree_index() {
+            None => return,
+            Some(tree_index) => tree_index,
+        };
+
+        let padding = self.padding();
+        let width = available_size.width() - padding.h_padding();
+        let height = available_size.height() - padding.v_padding();
+
+        let children = self.placed_children(gui, tree_index);
+        let column_sizes = self.column_sizes(gui, &children, Some(width.max(0.0)));
+        let row_sizes = self.row_sizes(gui, &children, &column_sizes, Some(height.max(0.0)));
+
+        let column_gap = self.column_gap.get();
+        let row_gap = self.row_gap.get();
+        self.column_layouts.replace(track_layouts(&column_sizes, column_gap));
+        self.row_layouts.replace(track_layouts(&row_sizes, row_gap));
+
+        if width < 0.0 || height <= 0.0 {
+            return;
+        }
+
+        for (child_index, cell) in children.iter() {
+            if let Some(child) = gui.get_widget(*child_index) {
+                let cell_size = Size::new(
+                    span_size(&column_sizes, cell.column, cell.column_span, column_gap),
+                    span_size(&row_sizes, cell.row, cell.row_span, row_gap),
+                );
+                child.update_content_size(gui, &cell_size);
+            }
+        }
+    }
+
+    fn compute_child_positions(&self, gui: &Gui) {
+        let tree_index = match self.get_tree_index() {
+            None => return,
+            Some(tree_index) => tree_index,
+        };
+
+        let column_layouts = self.column_layouts.borrow();
+        let row_layouts = self.row_layouts.borrow();
+        let cell_layout = |layouts: &[(f32, f32)], start: usize, span: usize| -> (f32, f32) {
+            let first = match layouts.get(start) {
+                None => return (0.0, 0.0),
+                Some(first) => *first,
+            };
+            let last = layouts[(start + span).min(layouts.len()) - 1];
+            (first.0, last.0 + last.1 - first.0)
+        };
+
+        for (child_index, cell) in self.placed_children(gui, tree_index) {
+            if let Some(w) = gui.get_widget(child_index) {
+                let (x, width) = cell_layout(&column_layouts, cell.column, cell.column_span);
+                let (y, height) = cell_layout(&row_layouts, cell.row, cell.row_span);
+                let position = Vector2::new(
+                    x - (width - w.widget_width()) * cell.alignment.horizontal.shift_factor(),
+                    y - (height - w.widget_height()) * cell.alignment.vertical.shift_factor(),
+                );
+                w.set_widget_target(&position);
+                w.update_child_positions(gui);
+            }
+        }
+    }
+
+    fn height_for_width(&self, gui: &Gui, width: f32) -> Option<f32> {
+        let tree_index = self.get_tree_index()?;
+        let padding = self.padding();
+
+        let children = self.placed_children(gui, tree_index);
+        let column_sizes = self.column_sizes(gui, &children, Some((width - padding.h_padding()).max(0.0)));
+        let row_sizes = self.row_sizes(gui, &children, &column_sizes, None);
+        let height = span_size(&row_sizes, 0, row_sizes.len(), self.row_gap.get());
+
+        Some(self.preferred_size().height().max(height + padding.v_padding()))
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, _gui: &Gui, _d: &mut impl RaylibDraw, _offset: &Vector2) {}
+}
@@ -13,13 +13,45 @@ use crate::text_style::TextStyle;
 use crate::widget::Widget;
 use crate::widget_operation::{LayoutableWidget};
 use crate::mouse::MouseState;
-use crate::event::Event;
-use std::cell::RefCell;
+use crate::keyboard::KeyboardState;
+use crate::event::{Event, MenuPar, DialogPar};
+use crate::dialog::{build_dialog, DialogChoice, DialogInfo};
+use crate::pane::PanePar;
+use crate::menu::{Menu, MenuPopup, MenuResponse};
+use std::cell::{Cell, RefCell};
+
+/// A subtree laid out against the whole available size and rendered above the layers below
+#[derive(Copy, Clone)]
+struct Layer {
+    root: Index,
+    /// a modal layer blocks the input of the layers below and dims them
+    modal: bool,
+}
+
+const MODAL_DIM_COLOR: Color = Color::new(0, 0, 0, 100);
 
 pub struct Gui {
     data: GuiData,
     tree: VecTree<Widget>,
+    /// the hidden root of the tree, parent of the root of every layer
+    base: Index,
+    /// the root of the main layer, below all the other layers
+    root: Option<Index>,
+    /// the overlay layers, from the bottom to the top
+    layers: RefCell<Vec<Layer>>,
+    /// the roots of the layers closed by the dialogs, hidden until `remove_closed_layers`
+    /// or `add_layer` removes them from the tree
+    closed_layers: RefCell<Vec<Index>>,
+    dialogs: RefCell<Vec<DialogInfo>>,
     events: RefCell<Arena<Event>>,
+    clip_stack: RefCell<Vec<Rectangle>>,
+    focused: Cell<Option<Index>>,
+    /// the time in seconds accumulated by `update_time`, used by the animated widgets
+    time: Cell<f64>,
+    /// the open menu, rendered above the widgets
+    menu_popup: RefCell<Option<MenuPopup>>,
+    /// the available size of the last layout
+    layout_size: Cell<Size>,
 }
 
 
@@ -28,21 +60,35 @@ pub struct GuiData {
     text_styles: HashMap<String, Rc<TextStyle>>,
     background: HashMap<String, Rc<Background>>,
     border: HashMap<String, Rc<Border>>,
+    images: HashMap<String, Rc<Texture2D>>,
 }
 
 
 impl Gui {
     pub fn new() -> Gui {
-        let tree = VecTree::new();
+        let mut tree = VecTree::new();
+        let base = tree.insert_root(Widget::Pane(PanePar::new()));
+        tree.get_mut(base).unwrap().tree_index = Some(base);
         return Gui {
             data: GuiData {
                 fonts: HashMap::new(),
                 text_styles: HashMap::new(),
                 background: HashMap::new(),
                 border: HashMap::new(),
+                images: HashMap::new(),
             },
             tree,
-            events: RefCell::new(Arena::new())
+            base,
+            root: None,
+            layers: RefCell::new(Vec::new()),
+            closed_layers: RefCell::new(Vec::new()),
+            dialogs: RefCell::new(Vec::new()),
+            events: RefCell::new(Arena::new()),
+            clip_stack: RefCell::new(Vec::new()),
+            focused: Cell::new(None),
+            time: Cell::new(0.0),
+            menu_popup: RefCell::new(None),
+            layout_size: Cell::new(Size::empty()),
         };
     }
 
@@ -53,6 +99,16 @@ impl Gui {
        self.render(d, &offset);
 
     }
+
+    /// Same as `layout_and_render`, the focused widget getting the keyboard too
+    pub fn layout_and_render_with_keyboard(&self, d:&mut impl RaylibDraw, available_size:&Size, mouse_state:&MouseState, keyboard_state:&KeyboardState, offset:&Vector2) {
+       self.update_states(&mouse_state.mouse_position(), &offset);
+       self.handle_events(&mouse_state, &offset);
+       self.handle_key_events(&keyboard_state);
+       self.layout(&available_size);
+       self.render(d, &offset);
+
+    }
 }
 
 ///style management (font/border/background)
@@ -86,6 +142,22 @@ impl Gui {
         self.data.fonts.get(font_name).cloned()
     }
 
+    /// image management
+    pub fn load_image(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread, image_name: &str, image_file: &str) -> Result<String, String> {
+        if self.data.images.contains_key(image_name) {
+            return Err(format!("An image with the name '{}' exists already", image_name));
+        }
+
+        let texture = rl.load_texture(thread, image_file)?;
+        let owned_image_name = image_name.to_owned();
+        self.data.images.insert(owned_image_name.clone(), Rc::new(texture));
+        Ok(owned_image_name)
+    }
+
+    pub fn get_image(&self, image_name: &str) -> Option<Rc<Texture2D>> {
+        self.data.images.get(image_name).cloned()
+    }
+
     pub fn add_text_style(&mut self, text_style_name: &str, font_name: &str, color: Color, spacing: f32) {
         if let Some(rc_font) = self.get_font(font_name) {
             let text_style = TextStyle::new(rc_font, color, spacing);
@@ -122,30 +194,65 @@ impl Gui {
 
 
     pub fn update_states(&self, mouse_position:&Vector2, offset:&Vector2) {
-        if let Some(root) = self.get_root() {
-            root.update_hoovered(self, offset, &mouse_position);
+        let layout_size = self.layout_size.get();
+        let over_menu = self.menu_popup.borrow_mut().as_mut()
+            .is_some_and(|popup| popup.update_hoovered(mouse_position, &layout_size));
+
+        // the widgets below the open menu, a modal layer or another layer are not hoovered
+        let mut mouse_position = if over_menu { None } else { Some(mouse_position) };
+        for layer in self.layers_from_top() {
+            if let Some(root) = self.get_widget(layer.root) {
+                let hit = root.update_hoovered_clipped(self, offset, mouse_position);
+                if hit || layer.modal {
+                    mouse_position = None;
+                }
+            }
         }
     }
 
     pub fn handle_events(&self, mouse_state:&MouseState, offset:&Vector2) {
         self.clear_events();
-        if let Some(root) = self.get_root() {
-            root.update_action(self, offset,mouse_state)
+        if self.handle_menu_events(mouse_state) {
+            return;
+        }
+        if mouse_state.left().is_pressed() {
+            // the focusable widget under the mouse, if any, requests the focus back
+            self.focused.set(None);
+        }
+        for layer in self.layers_from_top() {
+            if let Some(root) = self.get_widget(layer.root) {
+                root.update_action(self, offset,mouse_state)
+            }
+            if layer.modal {
+                break;
+            }
         }
+        self.handle_dialog_clicks();
     }
 
-
-    pub fn layout(&self, available_size: &Size) {
-        let option_root = self.tree.get_root_index();
-        if option_root.is_none() {
+    /// dispatch the keyboard state to the widget that has the focus
+    pub fn handle_key_events(&self, keyboard_state:&KeyboardState) {
+        if self.is_menu_open() && keyboard_state.is_pressed(KeyboardKey::KEY_ESCAPE) {
+            self.close_menu();
+            return;
+        }
+        if keyboard_state.is_pressed(KeyboardKey::KEY_ESCAPE) && self.dismiss_top_dialog() {
             return;
         }
-        let root_index = option_root.unwrap();
+        if let Some(focused) = self.focused.get().and_then(|idx| self.get_widget(idx)) {
+            focused.update_key_action(self, keyboard_state)
+        }
+    }
+
+    pub fn layout(&self, available_size: &Size) {
+        self.layout_size.set(*available_size);
+        self.update_styles(self.base);
 
-        self.update_styles(root_index);
-        self.update_computed_size(root_index);
-        self.update_content_size(root_index, &available_size);
-        self.update_widget_positions(root_index, &available_size)
+        for layer in self.layers_from_bottom() {
+            self.update_computed_size(layer.root);
+            self.update_content_size(layer.root, &available_size);
+            self.update_widget_positions(layer.root, &available_size)
+        }
     }
     fn update_styles(&self, root_index: Index) {
         self.tree.descendants(root_index).for_each(|idx| {
@@ -172,17 +279,263 @@ impl Gui {
         root.update_child_positions(&self);
     }
     pub fn render(&self, d: &mut impl RaylibDraw, position: &Vector2) {
-        if let Some(root) = self.get_root() {
-            root.render(&self, d, position)
+        for layer in self.layers_from_bottom() {
+            if layer.modal {
+                let size = self.layout_size.get();
+                d.draw_rectangle_rec(Rectangle::new(position.x, position.y, size.width(), size.height()), MODAL_DIM_COLOR);
+            }
+            if let Some(root) = self.get_widget(layer.root) {
+                root.render(&self, d, position)
+            }
+        }
+        if let Some(popup) = self.menu_popup.borrow().as_ref() {
+            popup.render(d);
+        }
+    }
+}
+
+/// focus management
+impl Gui {
+    pub fn focused_widget(&self) -> Option<Index> {
+        self.focused.get()
+    }
+
+    pub fn set_focus(&self, node_id: Index) {
+        self.focused.set(Some(node_id))
+    }
+
+    pub fn clear_focus(&self) {
+        self.focused.set(None)
+    }
+}
+
+/// layers & dialogs
+impl Gui {
+
+    /// Add a layer above the existing ones, with the provided widget as root.
+    /// A modal layer blocks the input of the layers below and dims them
+    pub fn add_layer(&mut self, root: Widget, modal: bool) -> Index {
+        self.remove_closed_layers();
+        let root_index = self.tree.insert(root, self.base);
+        self.tree.get_mut(root_index).unwrap().tree_index = Some(root_index);
+        self.layers.borrow_mut().push(Layer { root: root_index, modal });
+        if modal {
+            self.focused.set(None);
+        }
+        root_index
+    }
+
+    pub fn remove_layer(&mut self, root: Index) -> Option<Widget> {
+        if !self.layers.borrow().iter().any(|layer| layer.root == root) {
+            return None;
+        }
+        self.remove_widget(root)
+    }
+
+    /// Remove from the tree the widgets of the layers closed by the dialogs. The rendering
+    /// only borrows the gui, so this is called once per frame, before the layout, like
+    /// `gui.remove_closed_layers(); gui.layout_and_render_with_keyboard(...)`
+    pub fn remove_closed_layers(&mut self) {
+        let closed_layers: Vec<Index> = self.closed_layers.borrow_mut().drain(..).collect();
+        for root in closed_layers {
+            self.remove_widget(root);
+        }
+    }
+
+    /// Show a message in a modal dialog. A `DialogClosed` event with the provided id is
+    /// emitted when the user closes it
+    pub fn show_message(&mut self, dialog_id: &str, title: &str, message: &str) -> Index {
+        self.show_dialog(dialog_id, title, message, &[DialogChoice::Ok])
+    }
+
+    /// Ask for a confirmation in a modal dialog. A `DialogClosed` event with the provided id
+    /// and the choice of the user is emitted when the user closes it
+    pub fn show_confirm(&mut self, dialog_id: &str, title: &str, message: &str) -> Index {
+        self.show_dialog(dialog_id, title, message, &[DialogChoice::Ok, DialogChoice::Cancel])
+    }
+
+    fn show_dialog(&mut self, dialog_id: &str, title: &str, message: &str, choices: &[DialogChoice]) -> Index {
+        let dialog = build_dialog(self, dialog_id, title, message, choices);
+        let layer_root = dialog.layer_root;
+        self.dialogs.borrow_mut().push(dialog);
+        layer_root
+    }
+
+    fn layers_from_bottom(&self) -> Vec<Layer> {
+        let mut layers: Vec<Layer> = self.root.map(|root| Layer { root, modal: false }).into_iter().collect();
+        layers.extend(self.layers.borrow().iter());
+        layers
+    }
+
+    fn layers_from_top(&self) -> Vec<Layer> {
+        let mut layers = self.layers_from_bottom();
+        layers.reverse();
+        layers
+    }
+
+    /// Close the dialogs whose buttons were clicked, replacing the clicks by the dialog events
+    fn handle_dialog_clicks(&self) {
+        let clicks: Vec<(Index, String)> = self.events.borrow().iter()
+            .filter_map(|(idx, event)| match event {
+                Event::Click(par) => Some((idx, par.action_id().to_string())),
+                _ => None,
+            })
+            .collect();
+
+        for (event_index, action_id) in clicks {
+            let choice = self.dialogs.borrow().iter()
+                .find_map(|dialog| dialog.buttons.iter()
+                    .find(|(button_id, _)| *button_id == action_id)
+                    .map(|(_, choice)| (dialog.layer_root, *choice)));
+
+            if let Some((layer_root, choice)) = choice {
+                self.events.borrow_mut().remove(event_index);
+                self.close_dialog(layer_root, choice);
+            }
+        }
+    }
+
+    /// Close the top layer if it is a dialog. Returns true if a dialog was closed
+    fn dismiss_top_dialog(&self) -> bool {
+        let top = match self.layers.borrow().last() {
+            None => return false,
+            Some(layer) => layer.root,
+        };
+        let escape_choice = self.dialogs.borrow().iter()
+            .find(|dialog| dialog.layer_root == top)
+            .map(|dialog| dialog.escape_choice);
+
+        match escape_choice {
+            None => false,
+            Some(choice) => {
+                self.close_dialog(top, choice);
+                true
+            }
+        }
+    }
+
+    fn close_dialog(&self, layer_root: Index, choice: DialogChoice) {
+        let position = self.dialogs.borrow().iter().position(|dialog| dialog.layer_root == layer_root);
+        if let Some(position) = position {
+            let dialog = self.dialogs.borrow_mut().remove(position);
+            self.layers.borrow_mut().retain(|layer| layer.root != layer_root);
+            self.closed_layers.borrow_mut().push(layer_root);
+            self.add_event(Event::DialogClosed(DialogPar::new(&dialog.dialog_id, choice)));
+        }
+    }
+}
+
+/// menus
+impl Gui {
+
+    /// Open the menu at the provided position (in absolute coordinates), replacing the menu
+    /// currently open. The owner is the widget managing the menu, like a menu bar : the
+    /// left clicks on it do not close the menu and are dispatched to it
+    pub fn open_menu(&self, menu: Rc<Menu>, position: Vector2, text_style: Option<Rc<TextStyle>>, owner: Option<Index>) {
+        let popup = MenuPopup::new(menu, position, text_style, owner, &self.layout_size.get());
+        self.menu_popup.replace(Some(popup));
+    }
+
+    pub fn close_menu(&self) {
+        self.menu_popup.replace(None);
+    }
+
+    pub fn is_menu_open(&self) -> bool {
+        self.menu_popup.borrow().is_some()
+    }
+
+    /// The widget managing the current menu
+    pub fn menu_owner(&self) -> Option<Index> {
+        self.menu_popup.borrow().as_ref().and_then(|popup| popup.owner())
+    }
+
+    /// true if the provided menu is the one currently open
+    pub fn is_menu_opened(&self, menu: &Rc<Menu>) -> bool {
+        self.menu_popup.borrow().as_ref().is_some_and(|popup| Rc::ptr_eq(popup.root_menu(), menu))
+    }
+
+    /// Let the open menu handle the mouse. Returns true if the mouse actions must not
+    /// be dispatched to the widgets
+    fn handle_menu_events(&self, mouse_state: &MouseState) -> bool {
+        let response = match self.menu_popup.borrow().as_ref() {
+            None => return false,
+            Some(popup) => {
+                let owner_hoovered = popup.owner()
+                    .and_then(|owner| self.get_widget(owner))
+                    .is_some_and(|w| w.get_hoover_state());
+                popup.update_action(mouse_state, owner_hoovered)
+            }
+        };
+
+        match response {
+            MenuResponse::Ignored => false,
+            MenuResponse::Consumed => true,
+            MenuResponse::Close { consumed } => {
+                self.close_menu();
+                consumed
+            }
+            MenuResponse::Activated { id, checked } => {
+                self.close_menu();
+                self.add_event(Event::MenuActivated(MenuPar::new(&id, checked)));
+                true
+            }
+        }
+    }
+}
+
+/// time
+impl Gui {
+
+    /// Advance the time of the animations by the duration of the last frame, in seconds
+    pub fn update_time(&self, frame_time: f32) {
+        self.time.set(self.time.get() + frame_time as f64);
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time.get()
+    }
+}
+
+/// clipping
+impl Gui {
+
+    /// Restrict the rendering to the provided rectangle (in absolute coordinates).
+    /// Nested clips are intersected with the current one
+    pub fn push_clip(&self, clip: &Rectangle) {
+        let mut clip_stack = self.clip_stack.borrow_mut();
+        let clip = match clip_stack.last() {
+            None => *clip,
+            Some(current) => current.get_collision_rec(clip).unwrap_or_default()
+        };
+        clip_stack.push(clip);
+        apply_scissor(&clip);
+    }
+
+    /// Restore the clip that was active before the last call to `push_clip`
+    pub fn pop_clip(&self) {
+        let mut clip_stack = self.clip_stack.borrow_mut();
+        clip_stack.pop();
+        match clip_stack.last() {
+            None => unsafe { ffi::EndScissorMode() },
+            Some(clip) => apply_scissor(clip)
         }
     }
+
+    pub fn current_clip(&self) -> Option<Rectangle> {
+        self.clip_stack.borrow().last().cloned()
+    }
+}
+
+fn apply_scissor(clip: &Rectangle) {
+    unsafe { ffi::BeginScissorMode(clip.x as i32, clip.y as i32, clip.width as i32, clip.height as i32) }
 }
 
 /// tree management
 impl Gui {
 
+    /// The root of the main layer
     pub fn get_root(&self) -> Option<&Widget> {
-        self.tree.get_root_index().map(|idx| {self.tree.get(idx).unwrap()})
+        self.root.and_then(|idx| self.tree.get(idx))
     }
 
     pub fn get_parent(&self, node_id: Index) -> Option<Index> {
@@ -201,17 +554,51 @@ impl Gui {
     pub fn get_widget(&self, node_id: Index) -> Option<&Widget> {
         self.tree.get(node_id)
     }
+    /// Insert the root of the main layer
     pub fn insert_root(&mut self, root: Widget) -> Index {
-        let root_index = self.tree.insert_root(root);
+        if self.root.is_some() {
+            panic!("A root widget exists already");
+        }
+        let root_index = self.tree.insert(root, self.base);
         self.tree.get_mut(root_index)
             .unwrap()
             .tree_index = Some(root_index);
+        self.root = Some(root_index);
         root_index
     }
     pub fn add_child(&mut self, parent: Index, child: Widget) -> Index {
         let child_index = self.tree.insert(child, parent);
         self.tree.get_mut(child_index).unwrap().tree_index = Some(child_index);
+        self.invalidate_children_layout(parent);
         child_index
     }
+
+    /// Remove the widget and all its descendants from the tree
+    pub fn remove_widget(&mut self, node_id: Index) -> Option<Widget> {
+        self.layers.borrow_mut().retain(|layer| layer.root != node_id);
+        if self.root == Some(node_id) {
+            self.root = None;
+        }
+        let parent = self.tree.parent(node_id);
+        let mut removed = self.tree.remove(node_id)?;
+        removed.clear_tree_index();
+
+        if let Some(parent) = parent {
+            self.invalidate_children_layout(parent);
+        }
+        if let Some(focused) = self.focused.get() {
+            if !self.tree.contains(focused) {
+                self.focused.set(None);
+            }
+        }
+        Some(removed)
+    }
+
+    fn invalidate_children_layout(&self, parent: Index) {
+        if let Some(parent_widget) = self.get_widget(parent) {
+            parent_widget.invalidate_preferred_size(self);
+            parent_widget.invalidate_content_size(self);
+        }
+    }
 }
 
@@ -3,14 +3,21 @@ use crate::widget_operation::{LayoutableWidget, WidgetSpecific};
 use crate::gui::{Gui};
 use crate::size::{Size};
 use std::cell::Cell;
-use crate::fill::Fill;
+use crate::fill::{share_by_weight, Fill};
 use raylib::prelude::*;
 use std::ops::Deref;
 use crate::mouse::MouseState;
+use generational_arena::Index;
+use crate::alignment::{Justify, VAlignment};
+use crate::widget::Widget;
 
+/// A container placing its children from left to right. Each child is aligned vertically
+/// through its own alignment, `VAlignment::Baseline` aligning the baselines of their text,
+/// and takes the height of the box when it fills the height or is aligned with `VAlignment::Stretch`
 pub struct HBoxPar {
     widget_data: WidgetData,
     spacing: Cell<f32>,
+    justify: Cell<Justify>,
 }
 
 impl Deref for HBoxPar {
@@ -23,7 +30,7 @@ impl Deref for HBoxPar {
 
 impl HBoxPar {
     pub fn new() -> Self {
-        Self { widget_data: WidgetData::new(), spacing: Cell::new(10.0) }
+        Self { widget_data: WidgetData::new(), spacing: Cell::new(10.0), justify: Cell::new(Justify::Start) }
     }
 
     pub fn set_spacing(&self, gui: &Gui, spacing: f32) -> &HBoxPar {
@@ -38,6 +45,81 @@ impl HBoxPar {
     pub fn get_spacing(&self) -> f32 {
         self.spacing.get()
     }
+
+    /// How the children are spread along the width of the box when they do not fill it
+    pub fn set_justify(&self, gui: &Gui, justify: Justify) -> &HBoxPar {
+        if justify.eq(&self.justify.get()) {
+            return self;
+        }
+        self.justify.set(justify);
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn get_justify(&self) -> Justify {
+        self.justify.get()
+    }
+
+    /// The baseline of a child aligned on the baseline when it has the given height,
+    /// its bottom when it has no text
+    fn child_baseline(gui: &Gui, child: &Widget, height: f32) -> f32 {
+        child.baseline(gui, height).unwrap_or(height)
+    }
+
+    /// The height taken by children of the given heights, the ones aligned on the baseline
+    /// needing the largest part above the baseline plus the largest part below it
+    fn children_height(gui: &Gui, children: &[(&Widget, f32)]) -> f32 {
+        let mut max_height: f32 = 0.0;
+        let mut ascent: f32 = 0.0;
+        let mut descent: f32 = 0.0;
+        for (child, height) in children {
+            max_height = max_height.max(*height);
+            if child.alignment().vertical == VAlignment::Baseline {
+                let baseline = Self::child_baseline(gui, child, *height);
+                ascent = ascent.max(baseline);
+                descent = descent.max(height - baseline);
+            }
+        }
+        max_height.max(ascent + descent)
+    }
+
+    /// The width given to each child out of the content width of the box
+    fn child_widths(&self, gui: &Gui, tree_index: Index, width: f32) -> Vec<(Index, f32)> {
+        let mut summed_fixed_width:f32 = 0.0;
+        let mut shares = Vec::new();
+        let mut nb_children = 0;
+
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(child) = gui.get_widget(child_index) {
+                let fill = child.fill_width();
+                nb_children+=1;
+                match fill {
+                    Fill::Disabled => {
+                        summed_fixed_width += child.compute_computed_size(gui).width();
+                    }
+                    Fill::Enabled { weight} => {
+                        shares.push((weight, child.min_size().width(), child.max_size().width()));
+                    }
+                }
+            }
+        }
+
+        let space_taken_by_spacing = self.spacing.get() * ((nb_children - 1).max(0) as f32);
+
+        let mut fill_widths = share_by_weight(width - space_taken_by_spacing - summed_fixed_width, &shares).into_iter();
+
+        let mut child_widths = Vec::new();
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(child) = gui.get_widget(child_index) {
+                let child_width = match child.fill_width() {
+                    Fill::Disabled => child.compute_computed_size(gui).width(),
+                    Fill::Enabled { .. } => fill_widths.next().unwrap_or(0.0),
+                };
+                child_widths.push((child_index, child_width));
+            }
+        }
+        child_widths
+    }
 }
 
 impl WidgetSpecific for HBoxPar {
@@ -60,17 +142,18 @@ impl WidgetSpecific for HBoxPar {
         let tree_index = tree_index.unwrap();
 
         let mut nb_children = 0;
-        let mut max_height: f32 = 0.0;
+        let mut child_heights = Vec::new();
         let mut summed_width: f32 = 0.0;
 
         for child_index in gui.get_widget_children(tree_index) {
             if let Some(child) = gui.get_widget(child_index) {
                 let child_computed_size = child.compute_computed_size(gui);
                 nb_children += 1;
-                max_height = max_height.max(child_computed_size.height());
+                child_heights.push((child, child_computed_size.height()));
                 summed_width += child_computed_size.width();
             }
         }
+        let max_height = Self::children_height(gui, &child_heights);
         let spacing = self.spacing.get();
         summed_width += spacing * ((nb_children - 1).max(0) as f32);
 
@@ -91,55 +174,37 @@ impl WidgetSpecific for HBoxPar {
         }
         let tree_index = tree_index.unwrap();
 
-        let mut summed_fixed_width:f32 = 0.0;
-        let mut summed_weight:u32 = 0;
-        let mut nb_children = 0;
-
-        for child_index in gui.get_widget_children(tree_index) {
-            if let Some(child) = gui.get_widget(child_index) {
-                let fill = child.fill_width();
-                nb_children+=1;
-                match fill {
-                    Fill::Disabled => {
-                        summed_fixed_width += child.compute_computed_size(gui).width();
-                    }
-                    Fill::Enabled { weight} => {
-                        summed_weight += weight;
-                    }
-                }
-            }
-        }
-
         let padding = self.padding();
         let width = available_size.width() - padding.h_padding();
         let height= available_size.height() - padding.v_padding();
 
-        let space_taken_by_spacing = self.spacing.get() * ((nb_children - 1).max(0) as f32);
-
-        let fill_width = (width - space_taken_by_spacing - summed_fixed_width)/(summed_weight.max(1) as f32);
-
         if width<0.0 || height<=0.0 {
             return
         }
 
         let mut size = Size::new(0.0,height);
-        for child_index in gui.get_widget_children(tree_index) {
+        for (child_index, child_width) in self.child_widths(gui, tree_index, width) {
             if let Some(child) = gui.get_widget(child_index) {
-                let fill = child.fill_width();
-                match fill {
-                    Fill::Disabled => {
-                        let child_width = child.compute_computed_size(gui).width();
-                        size.set_width(child_width);
-                        child.update_content_size(gui,&size);
-                    }
-                    Fill::Enabled { weight } => {
-                        size.set_width(fill_width * weight as f32);
-                        child.update_content_size(gui, &size)
-                    }
-                }
+                size.set_width(child_width);
+                child.update_content_size(gui,&size);
+            }
+        }
+
+    }
+
+    fn height_for_width(&self, gui: &Gui, width: f32) -> Option<f32> {
+        let tree_index = self.get_tree_index()?;
+        let padding = self.padding();
+
+        let mut child_heights = Vec::new();
+        for (child_index, child_width) in self.child_widths(gui, tree_index, width - padding.h_padding()) {
+            if let Some(child) = gui.get_widget(child_index) {
+                child_heights.push((child, child.compute_height_for_width(gui, child_width)));
             }
         }
+        let max_height = Self::children_height(gui, &child_heights);
 
+        Some(self.preferred_size().height().max(max_height + padding.v_padding()))
     }
 
     fn compute_child_positions(&self, gui: &Gui) {
@@ -155,19 +220,26 @@ impl WidgetSpecific for HBoxPar {
         };
 
         let spacing = self.spacing.get();
-
-        let mut position = Vector2::new(0.0,0.0);
-        for child_index in gui.get_widget_children(tree_index) {
-            if let Some(w) = gui.get_widget(child_index) {
-                {
-                    let widget_height = w.widget_height();
-                    position.y = (content_size.height() - widget_height)*0.5;
-                    w.set_widget_target(&position);
-                    w.update_child_positions(gui);
-                }
-
-                position.x += w.widget_width() + spacing;
+        let children: Vec<&Widget> = gui.get_widget_children(tree_index).filter_map(|child_index| gui.get_widget(child_index)).collect();
+
+        let used_width = children.iter().map(|w| w.widget_width()).sum::<f32>() + spacing * ((children.len() as i32 - 1).max(0) as f32);
+        let (start, extra_spacing) = self.justify.get().distribute(content_size.width() - used_width, children.len());
+        let baseline = children.iter()
+            .filter(|w| w.alignment().vertical == VAlignment::Baseline)
+            .fold(0.0f32, |baseline, w| baseline.max(Self::child_baseline(gui, w, w.widget_height())));
+
+        let mut position = Vector2::new(start,0.0);
+        for w in children {
+            {
+                position.y = match w.alignment().vertical {
+                    VAlignment::Baseline => baseline - Self::child_baseline(gui, w, w.widget_height()),
+                    alignment => -(content_size.height() - w.widget_height()) * alignment.shift_factor(),
+                };
+                w.set_widget_target(&position);
+                w.update_child_positions(gui);
             }
+
+            position.x += w.widget_width() + spacing + extra_spacing;
         }
     }
 
@@ -0,0 +1,185 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+use std::rc::Rc;
+
+use raylib::prelude::*;
+
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+/// How the image is scaled to the content of the widget
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ImageScaling {
+    /// the image keeps its size and is clipped to the content
+    None,
+    /// the image is scaled, keeping its aspect ratio, to be fully visible
+    Fit,
+    /// the image is scaled, keeping its aspect ratio, to cover the whole content. The parts outside are cropped
+    Fill,
+    /// the image is scaled to the content, ignoring its aspect ratio
+    Stretch,
+    /// the image is repeated to cover the content
+    Tile,
+}
+
+/// A widget displaying an image loaded with `Gui::load_image`.
+/// Its preferred size is the size of the image, the dimension that is not provided
+/// by the user preferred size is computed with the aspect ratio of the image
+pub struct ImagePar {
+    widget_data: WidgetData,
+    image_name: RefCell<String>,
+    image: RefCell<Option<Rc<Texture2D>>>,
+    scaling: Cell<ImageScaling>,
+    tint: Cell<Color>,
+}
+
+impl Deref for ImagePar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl ImagePar {
+    pub fn new() -> Self {
+        Self {
+            widget_data: WidgetData::new(),
+            image_name: RefCell::new("".to_string()),
+            image: RefCell::new(None),
+            scaling: Cell::new(ImageScaling::Fit),
+            tint: Cell::new(Color::WHITE),
+        }
+    }
+
+    pub fn set_image(&self, gui: &Gui, image_name: &str) -> &ImagePar {
+        self.image_name.replace(image_name.to_string());
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn scaling(&self) -> ImageScaling {
+        self.scaling.get()
+    }
+
+    pub fn set_scaling(&self, scaling: ImageScaling) -> &ImagePar {
+        self.scaling.set(scaling);
+        self
+    }
+
+    pub fn tint(&self) -> Color {
+        self.tint.get()
+    }
+
+    /// The color multiplied with the pixels of the image (white keeps the original colors)
+    pub fn set_tint(&self, tint: Color) -> &ImagePar {
+        self.tint.set(tint);
+        self
+    }
+
+    fn image_size(&self) -> Size {
+        match self.image.borrow().as_ref() {
+            None => Size::empty(),
+            Some(texture) => Size::new(texture.width() as f32, texture.height() as f32),
+        }
+    }
+}
+
+impl WidgetSpecific for ImagePar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, gui: &Gui) -> Size {
+        self.image.replace(gui.get_image(&self.image_name.borrow()));
+
+        let image_size = self.image_size();
+        let mut size = self.preferred_size();
+        if !image_size.is_empty() {
+            // keep the aspect ratio of the image, padding excluded, when only one dimension is provided
+            let padding = self.padding();
+            if size.width() > 0.0 && size.height() <= 0.0 {
+                let width = (size.width() - padding.h_padding()).max(0.0);
+                size.set_height(width * image_size.height() / image_size.width() + padding.v_padding());
+            } else if size.height() > 0.0 && size.width() <= 0.0 {
+                let height = (size.height() - padding.v_padding()).max(0.0);
+                size.set_width(height * image_size.width() / image_size.height() + padding.h_padding());
+            }
+        }
+        size.replace_empty_dimensions(&image_size.with_padding(&self.padding()));
+        size
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {}
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let borrowed_image = self.image.borrow();
+        let texture = match borrowed_image.as_ref() {
+            None => return,
+            Some(texture) => texture.as_ref(),
+        };
+
+        let mut content_layout = self.content_layout();
+        content_layout.x += offset.x;
+        content_layout.y += offset.y;
+
+        let image_size = self.image_size();
+        if image_size.is_empty() || content_layout.width <= 0.0 || content_layout.height <= 0.0 {
+            return;
+        }
+        let full_source = Rectangle::new(0.0, 0.0, image_size.width(), image_size.height());
+        let tint = self.tint.get();
+
+        match self.scaling.get() {
+            ImageScaling::None => {
+                gui.push_clip(&content_layout);
+                d.draw_texture_rec(texture, full_source, Vector2::new(content_layout.x, content_layout.y), tint);
+                gui.pop_clip();
+            }
+            ImageScaling::Fit => {
+                let scale = (content_layout.width / image_size.width()).min(content_layout.height / image_size.height());
+                let width = image_size.width() * scale;
+                let height = image_size.height() * scale;
+                let destination = Rectangle::new(
+                    content_layout.x + (content_layout.width - width) * 0.5,
+                    content_layout.y + (content_layout.height - height) * 0.5,
+                    width,
+                    height,
+                );
+                d.draw_texture_pro(texture, full_source, destination, Vector2::zero(), 0.0, tint);
+            }
+            ImageScaling::Fill => {
+                // crop the source to the aspect ratio of the content
+                let scale = (content_layout.width / image_size.width()).max(content_layout.height / image_size.height());
+                let source_width = content_layout.width / scale;
+                let source_height = content_layout.height / scale;
+                let source = Rectangle::new(
+                    (image_size.width() - source_width) * 0.5,
+                    (image_size.height() - source_height) * 0.5,
+                    source_width,
+                    source_height,
+                );
+                d.draw_texture_pro(texture, source, content_layout, Vector2::zero(), 0.0, tint);
+            }
+            ImageScaling::Stretch => {
+                d.draw_texture_pro(texture, full_source, content_layout, Vector2::zero(), 0.0, tint);
+            }
+            ImageScaling::Tile => {
+                d.draw_texture_tiled(texture, full_source, content_layout, Vector2::zero(), 0.0, 1.0, tint);
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use raylib::prelude::*;
+
+/// The keys whose pressed state is tracked for the widgets
+const TRACKED_KEYS: [KeyboardKey; 15] = [
+    KeyboardKey::KEY_UP,
+    KeyboardKey::KEY_DOWN,
+    KeyboardKey::KEY_LEFT,
+    KeyboardKey::KEY_RIGHT,
+    KeyboardKey::KEY_PAGE_UP,
+    KeyboardKey::KEY_PAGE_DOWN,
+    KeyboardKey::KEY_HOME,
+    KeyboardKey::KEY_END,
+    KeyboardKey::KEY_ENTER,
+    KeyboardKey::KEY_KP_ENTER,
+    KeyboardKey::KEY_SPACE,
+    KeyboardKey::KEY_ESCAPE,
+    KeyboardKey::KEY_BACKSPACE,
+    KeyboardKey::KEY_DELETE,
+    KeyboardKey::KEY_TAB,
+];
+
+pub struct KeyboardState {
+    /// the tracked keys that switched from up to down since the last frame
+    pressed_keys: Vec<KeyboardKey>,
+    /// the characters typed since the last frame
+    typed_chars: Vec<char>,
+    shift_down: bool,
+    control_down: bool,
+}
+
+impl KeyboardState {
+    pub fn new() -> Self {
+        Self {
+            pressed_keys: Vec::new(),
+            typed_chars: Vec::new(),
+            shift_down: false,
+            control_down: false,
+        }
+    }
+
+    pub fn update(&mut self, d: &RaylibDrawHandle) {
+        self.pressed_keys.clear();
+        for key in TRACKED_KEYS.iter() {
+            if d.is_key_pressed(*key) {
+                self.pressed_keys.push(*key);
+            }
+        }
+
+        self.typed_chars.clear();
+        loop {
+            let codepoint = unsafe { ffi::GetCharPressed() };
+            if codepoint <= 0 {
+                break;
+            }
+            if let Some(c) = std::char::from_u32(codepoint as u32) {
+                self.typed_chars.push(c);
+            }
+        }
+
+        self.shift_down = d.is_key_down(KeyboardKey::KEY_LEFT_SHIFT) || d.is_key_down(KeyboardKey::KEY_RIGHT_SHIFT);
+        self.control_down = d.is_key_down(KeyboardKey::KEY_LEFT_CONTROL) || d.is_key_down(KeyboardKey::KEY_RIGHT_CONTROL);
+    }
+
+    pub fn is_pressed(&self, key: KeyboardKey) -> bool {
+        self.pressed_keys.contains(&key)
+    }
+
+    pub fn pressed_keys(&self) -> &[KeyboardKey] {
+        &self.pressed_keys
+    }
+
+    pub fn typed_chars(&self) -> &[char] {
+        &self.typed_chars
+    }
+
+    pub fn is_shift_down(&self) -> bool {
+        self.shift_down
+    }
+
+    pub fn is_control_down(&self) -> bool {
+        self.control_down
+    }
+}
@@ -10,11 +10,43 @@ use crate::widget_operation::{ WidgetSpecific};
 use crate::gui::{Gui};
 use crate::size::{Size};
 use crate::mouse::MouseState;
+use crate::alignment::HAlignment;
+use crate::text_style::TextStyle;
+use crate::event::Event::LinkClicked;
+use crate::event::LinkPar;
+use crate::rich_text::{LINK_COLOR, LINK_HOOVER_COLOR};
 
+/// How the text of a label is broken into lines when it is wider than the label
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum WrapMode {
+    /// only the line breaks of the text start new lines
+    None,
+    /// lines are broken between words, and inside the words too long to fit on a line
+    Word,
+    /// lines are broken between any two characters
+    Char,
+}
+
+const LABEL_ELLIPSIS: &str = "...";
+const LABEL_BORDER: f32 = 3.0;
+
+/// A text on one or several lines, drawn with a single text style. The whole text can be
+/// a link, see `RichTextPar` for links on parts of a text
 pub struct LabelPar {
     widget_data: WidgetData,
     text: RefCell<Option<String>>,
-    text_size: Cell<Size>
+    text_size: Cell<Size>,
+    wrap_mode: Cell<WrapMode>,
+    max_lines: Cell<Option<usize>>,
+    text_alignment: Cell<HAlignment>,
+    line_height: Cell<f32>,
+    /// the lines displayed with their sizes, broken for the width of the label by the position step
+    lines: RefCell<Vec<(String, Size)>>,
+    /// the id sent by a `LinkClicked` event when the text is clicked
+    link: RefCell<Option<String>>,
+    link_hoovered: Cell<bool>,
+    /// the press started on the link, which is clicked if the release happens on it too
+    link_armed: Cell<bool>,
 }
 
 impl Deref for LabelPar {
@@ -30,7 +62,15 @@ impl LabelPar {
         let label_par = Self {
             widget_data: WidgetData::new(),
             text: RefCell::new(None),
-            text_size: Cell::new(Size::empty())
+            text_size: Cell::new(Size::empty()),
+            wrap_mode: Cell::new(WrapMode::None),
+            max_lines: Cell::new(None),
+            text_alignment: Cell::new(HAlignment::Middle),
+            line_height: Cell::new(1.0),
+            lines: RefCell::new(Vec::new()),
+            link: RefCell::new(None),
+            link_hoovered: Cell::new(false),
+            link_armed: Cell::new(false),
         };
         label_par.set_hooverable(true);
         label_par
@@ -58,19 +98,200 @@ impl LabelPar {
         self
     }
 
-    fn measure_text(&self) -> Size {
+    /// Make the whole text a single link, clicking on a line emits a `LinkClicked` event with the id
+    /// instead of the click of the label. A label has no link spans : a text mixing plain parts
+    /// and links, each with its own id, is made with a `RichTextPar` and its `[link=id]` spans
+    pub fn set_link(&self, gui: &Gui, link_id: Option<&str>) -> &LabelPar {
+        self.link.replace(link_id.map(|link_id| link_id.to_owned()));
+        self.link_hoovered.set(false);
+        self.link_armed.set(false);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn wrap_mode(&self) -> WrapMode {
+        self.wrap_mode.get()
+    }
+
+    /// With wrapping the preferred width of the label is the one of its longest line, unless
+    /// the preferred size sets the width to wrap the text at
+    pub fn set_wrap_mode(&self, gui: &Gui, wrap_mode: WrapMode) -> &LabelPar {
+        self.wrap_mode.set(wrap_mode);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// Keep at most `max_lines` lines, the last one ending with an ellipsis when text is cut.
+    /// A single line label also gets an ellipsis when it is wider than the label
+    pub fn set_max_lines(&self, gui: &Gui, max_lines: Option<usize>) -> &LabelPar {
+        self.max_lines.set(max_lines.filter(|max_lines| *max_lines > 0));
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// The alignment of each line inside the label, centered by default
+    pub fn set_text_alignment(&self, gui: &Gui, alignment: HAlignment) -> &LabelPar {
+        self.text_alignment.set(alignment);
+        self.invalidate_position(gui);
+        self
+    }
+
+    /// The distance between two lines as a factor of the height of the font
+    pub fn set_line_height(&self, gui: &Gui, line_height: f32) -> &LabelPar {
+        self.line_height.set(line_height.max(0.0));
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// Break the text in lines no wider than `width`, with no limit with `None`
+    fn break_text(&self, text_style: &TextStyle, width: Option<f32>) -> Vec<(String, Size)> {
         match self.text.borrow().as_ref() {
-            None => Size::empty(),
+            None => Vec::new(),
             Some(text) => {
-                match self.text_style() {
-                    None => Size::empty(),
-                    Some(text_style) => {
-                        text_style.measure_text(text)
+                let fits = |line: &str| width.is_none_or(|width| text_style.measure_text(line).width() <= width);
+                break_lines(text, self.wrap_mode.get(), self.max_lines.get(), &fits).into_iter()
+                    .map(|line| {
+                        let size = text_style.measure_text(&line);
+                        (line, size)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// The size of the whole text broken for `width`, without changing the displayed lines
+    fn measure_lines(&self, width: Option<f32>) -> Size {
+        let text_style = match self.text_style() {
+            None => return Size::empty(),
+            Some(text_style) => text_style,
+        };
+        let lines = self.break_text(&text_style, width);
+        let line_advance = self.line_advance(&text_style);
+        let mut size = Size::empty();
+        for (index, (_, line_size)) in lines.iter().enumerate() {
+            size.set_width(size.width().max(line_size.width()));
+            let line_bottom = index as f32 * line_advance + line_size.height();
+            size.set_height(size.height().max(line_bottom));
+        }
+        size
+    }
+
+    fn measure_text(&self) -> Size {
+        let preferred_width = self.preferred_size().width();
+        let wrap_width = if self.wrap_mode.get() != WrapMode::None && preferred_width > 0.0 {
+            Some(preferred_width - self.padding().h_padding() - 2.0 * LABEL_BORDER)
+        } else {
+            None
+        };
+        self.measure_lines(wrap_width)
+    }
+
+    /// The layouts of the displayed lines, in the same coordinates as the content layout
+    fn line_layouts(&self, text_style: &TextStyle) -> Vec<Rectangle> {
+        let lines = self.lines.borrow();
+        let content_layout = self.content_layout();
+        let line_advance = self.line_advance(text_style);
+        let top = content_layout.y + (content_layout.height - self.text_height(text_style, &lines))*0.5;
+        lines.iter().enumerate().map(|(index, (_, line_size))| {
+            let x = match self.text_alignment.get() {
+                HAlignment::Left | HAlignment::Stretch => content_layout.x + LABEL_BORDER,
+                HAlignment::Middle => content_layout.x + (content_layout.width - line_size.width())*0.5,
+                HAlignment::Right => content_layout.x + content_layout.width - LABEL_BORDER - line_size.width(),
+            };
+            Rectangle::new(x, top + index as f32 * line_advance, line_size.width(), line_size.height())
+        }).collect()
+    }
+
+    fn line_advance(&self, text_style: &TextStyle) -> f32 {
+        text_style.measure_text("Ag").height() * self.line_height.get()
+    }
+
+    /// The height of the displayed lines, from the top of the first one to the bottom of the last one
+    fn text_height(&self, text_style: &TextStyle, lines: &[(String, Size)]) -> f32 {
+        let line_advance = self.line_advance(text_style);
+        lines.iter().enumerate()
+            .fold(0.0f32, |height, (index, (_, size))| height.max(index as f32 * line_advance + size.height()))
+    }
+
+    /// The width the text has to fit in, none when it can overflow the label
+    fn text_width_limit(&self, widget_width: f32) -> Option<f32> {
+        if self.wrap_mode.get() == WrapMode::None && self.max_lines.get().is_none() {
+            return None;
+        }
+        Some((widget_width - self.padding().h_padding() - 2.0 * LABEL_BORDER).max(0.0))
+    }
+}
+
+/// Split the text in lines for which `fits` is true, according to the wrap mode,
+/// and cut the text at `max_lines` with an ellipsis
+fn break_lines(text: &str, wrap_mode: WrapMode, max_lines: Option<usize>, fits: &impl Fn(&str) -> bool) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for paragraph in text.split('\n') {
+        match wrap_mode {
+            _ if fits(paragraph) => lines.push(paragraph.to_string()),
+            WrapMode::None => lines.push(paragraph.to_string()),
+            WrapMode::Char => break_chars(paragraph, fits, &mut lines),
+            WrapMode::Word => {
+                let mut line = String::new();
+                for word in paragraph.split(' ') {
+                    let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+                    if fits(&candidate) {
+                        line = candidate;
+                        continue;
+                    }
+                    if !line.is_empty() {
+                        lines.push(line);
+                    }
+                    if fits(word) {
+                        line = word.to_string();
+                    } else {
+                        // a word too long for a line is broken between its characters
+                        break_chars(word, fits, &mut lines);
+                        line = lines.pop().unwrap_or_default();
                     }
                 }
+                lines.push(line);
             }
         }
     }
+
+    if let Some(max_lines) = max_lines {
+        let cut = lines.len() > max_lines;
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            if cut || !fits(last) {
+                *last = with_ellipsis(last, fits);
+            }
+        }
+    }
+
+    lines
+}
+
+fn break_chars(text: &str, fits: &impl Fn(&str) -> bool, lines: &mut Vec<String>) {
+    let mut line = String::new();
+    for c in text.chars() {
+        line.push(c);
+        if !fits(&line) && line.chars().count() > 1 {
+            line.pop();
+            lines.push(line);
+            line = c.to_string();
+        }
+    }
+    lines.push(line);
+}
+
+/// Remove characters from the end of the line until it fits with the ellipsis
+fn with_ellipsis(line: &str, fits: &impl Fn(&str) -> bool) -> String {
+    let mut line = line.trim_end().to_string();
+    loop {
+        let candidate = format!("{}{}", line, LABEL_ELLIPSIS);
+        if fits(&candidate) || line.is_empty() {
+            return candidate;
+        }
+        line.pop();
+        line.truncate(line.trim_end().len());
+    }
 }
 
 
@@ -84,13 +305,15 @@ impl WidgetSpecific for LabelPar {
         &mut self.widget_data
     }
 
-    fn compute_size(&self, _gui: &Gui) -> Size {
+    fn compute_size(&self, gui: &Gui) -> Size {
         let padding = self.padding();
         let text_size = self.measure_text();
 
         self.text_size.set(text_size);
+        // the displayed lines are broken again by the position step, even if the size is unchanged
+        self.invalidate_position(gui);
 
-        let text_size_with_padding = text_size.with_padding(&padding).width_border(3.0);
+        let text_size_with_padding = text_size.with_padding(&padding).width_border(LABEL_BORDER);
 
         let mut preferred = self.preferred_size();
 
@@ -101,26 +324,133 @@ impl WidgetSpecific for LabelPar {
     fn compute_child_content_size(&self, _gui: &Gui, _available_space: Size) {}
 
     fn compute_child_positions(&self, _gui: &Gui) {
+        let lines = match self.text_style() {
+            None => Vec::new(),
+            Some(text_style) => self.break_text(&text_style, self.text_width_limit(self.widget_width())),
+        };
+        self.lines.replace(lines);
+    }
+
+    fn height_for_width(&self, _gui: &Gui, width: f32) -> Option<f32> {
+        if self.wrap_mode.get() == WrapMode::None {
+            return None;
+        }
+        let text_size = self.measure_lines(self.text_width_limit(width));
+        let height = text_size.height() + self.padding().v_padding() + 2.0 * LABEL_BORDER;
+        Some(self.preferred_size().height().max(height))
+    }
+
+    fn baseline(&self, _gui: &Gui, height: f32) -> Option<f32> {
+        let text_style = self.text_style()?;
+        // asked while the parent is laid out, so the lines are broken for the current width
+        let lines = self.break_text(&text_style, self.text_width_limit(self.widget_width()));
+        if lines.is_empty() {
+            return None;
+        }
+        // the lines are centered in the content
+        let padding = self.padding();
+        let content_height = height - padding.v_padding();
+        Some(padding.top + (content_height - self.text_height(&text_style, &lines)) * 0.5 + text_style.font().baseline())
     }
 
     fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
         self.widget_data.wd_update_action(gui,offset,mouse_state);
+
+        let link = self.link.borrow();
+        let (link_id, text_style) = match (link.as_ref(), self.text_style()) {
+            (Some(link_id), Some(text_style)) => (link_id, text_style),
+            _ => {
+                self.link_hoovered.set(false);
+                self.link_armed.set(false);
+                return;
+            }
+        };
+        let mouse_position = mouse_state.mouse_position();
+        let local = Vector2::new(mouse_position.x - offset.x, mouse_position.y - offset.y);
+        let hoovered = self.get_hoover_state() &&
+            self.line_layouts(&text_style).iter().any(|layout| layout.check_collision_point_rec(local));
+        self.link_hoovered.set(hoovered);
+
+        // like the click of the label, the link is armed by the press and clicked by the release.
+        // A press on the link does not arm the label, so it does not send its click
+        let mut link_armed = self.link_armed.get();
+        if mouse_state.left().is_pressed() && hoovered {
+            link_armed = true;
+            self.state.armed.set(false);
+        }
+        if mouse_state.left().is_released() && hoovered && link_armed {
+            gui.add_event(LinkClicked(LinkPar::new(link_id)))
+        }
+        self.link_armed.set(link_armed && mouse_state.left().is_down());
     }
 
     fn render_my_visual(&self, _gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
-        if let Some(text) = self.text.borrow().as_ref() {
-            let content_layout = self.content_layout();
-            let text_size = self.text_size.get();
-            let position = Vector2 {
-                x: content_layout.x + offset.x + (content_layout.width - text_size.width())*0.5,
-                y: content_layout.y + offset.y + (content_layout.height - text_size.height())*0.5,
-            };
-
-            if let Some(text_style) = self.text_style() {
-                text_style.draw_text(d, text, &position)
+        let text_style = match self.text_style() {
+            None => return,
+            Some(text_style) => text_style,
+        };
+        let is_link = self.link.borrow().is_some();
+        let hoovered = self.link_hoovered.get();
+        let lines = self.lines.borrow();
+        for ((line, _), layout) in lines.iter().zip(self.line_layouts(&text_style)) {
+            let position = Vector2::new(layout.x + offset.x, layout.y + offset.y);
+            if !is_link {
+                text_style.draw_text(d, line, &position);
+                continue;
+            }
+            let color = if hoovered { LINK_HOOVER_COLOR } else { LINK_COLOR };
+            text_style.draw_text_with_color(d, line, &position, color);
+            if hoovered {
+                let y = position.y + text_style.font().baseline() + 1.0;
+                d.draw_line_v(Vector2::new(position.x, y), Vector2::new(position.x + layout.width, y), color);
             }
         }
     }
 
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{break_lines, WrapMode};
+
+    fn fits(max_chars: usize) -> impl Fn(&str) -> bool {
+        move |line: &str| line.chars().count() <= max_chars
+    }
+
+    #[test]
+    fn keeps_the_lines_without_wrapping() {
+        assert_eq!(break_lines("hello world\nbye", WrapMode::None, None, &fits(5)), vec!["hello world", "bye"]);
+    }
+
+    #[test]
+    fn breaks_between_words() {
+        assert_eq!(break_lines("hello big world", WrapMode::Word, None, &fits(9)), vec!["hello big", "world"]);
+    }
+
+    #[test]
+    fn breaks_a_word_longer_than_a_line() {
+        assert_eq!(break_lines("a verylongword", WrapMode::Word, None, &fits(5)), vec!["a", "veryl", "ongwo", "rd"]);
+    }
+
+    #[test]
+    fn breaks_between_characters() {
+        assert_eq!(break_lines("abcdefg", WrapMode::Char, None, &fits(3)), vec!["abc", "def", "g"]);
+    }
+
+    #[test]
+    fn keeps_a_character_wider_than_a_line() {
+        assert_eq!(break_lines("ab", WrapMode::Char, None, &fits(0)), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn cuts_the_text_at_max_lines_with_an_ellipsis() {
+        assert_eq!(break_lines("one two three", WrapMode::Word, Some(1), &fits(7)), vec!["one..."]);
+        assert_eq!(break_lines("one two", WrapMode::Word, Some(2), &fits(7)), vec!["one two"]);
+    }
+
+    #[test]
+    fn ends_a_single_line_too_wide_with_an_ellipsis() {
+        assert_eq!(break_lines("abcdef", WrapMode::None, Some(1), &fits(5)), vec!["ab..."]);
+    }
+}
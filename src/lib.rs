@@ -22,10 +22,35 @@ pub mod border;
 pub mod text_style;
 pub mod fill;
 pub mod background;
+pub mod color;
 pub mod position;
 pub mod event;
 pub mod hbox;
 pub mod slider;
+pub mod orientation;
+pub mod selection;
+pub mod scroll_bar;
+pub mod list_view;
+pub mod table;
+pub mod keyboard;
+pub mod tree_view;
+pub mod tab_pane;
+pub mod scroll_pane;
+pub mod overflow;
+pub mod progress_bar;
+pub mod image;
+pub mod menu;
+pub mod menu_bar;
+pub mod dialog;
+pub mod split_pane;
+pub mod color_picker;
+pub mod range_slider;
+pub mod canvas;
+pub mod chart;
+pub mod rich_text;
+pub mod grid;
+pub mod flow;
+pub mod stack;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,382 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+
+use raylib::prelude::*;
+
+use crate::event::Event::Select;
+use crate::event::SelectPar;
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::orientation::Orientation::Vertical;
+use crate::scroll_bar::{ScrollBar, SCROLL_BAR_THICKNESS};
+use crate::selection::{Selection, SelectionMode};
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+/// The height of the rows of a list
+pub enum RowHeight {
+    /// all the rows have the same height
+    Fixed(f32),
+    /// the height of each row is given by the callback
+    Variable(Box<dyn Fn(usize) -> f32>),
+}
+
+/// Build the text of the row from the index of the item
+pub type RowBuilder = Box<dyn Fn(usize) -> String>;
+
+/// A row currently displayed by the list
+struct VisibleRow {
+    index: usize,
+    text: String,
+    text_size: Size,
+    /// layout of the row, in the same coordinates as the content layout of the list
+    layout: Rectangle,
+}
+
+/// A list that only builds and lays out its visible rows. The rows are
+/// obtained from a row builder callback called with the index of the item.
+pub struct ListViewPar {
+    widget_data: WidgetData,
+    item_count: Cell<usize>,
+    row_builder: RefCell<Option<RowBuilder>>,
+    row_height: RefCell<RowHeight>,
+    /// cumulated heights of the rows, only used with variable row heights
+    row_offsets: RefCell<Vec<f32>>,
+    row_offsets_valid: Cell<bool>,
+    visible_rows: RefCell<Vec<VisibleRow>>,
+    hoovered_row: Cell<Option<usize>>,
+    selection: RefCell<Selection>,
+    scroll_bar: ScrollBar,
+}
+
+impl Deref for ListViewPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+const LIST_DEFAULT_WIDTH: f32 = 200.0;
+const LIST_DEFAULT_HEIGHT: f32 = 200.0;
+const LIST_DEFAULT_ROW_HEIGHT: f32 = 30.0;
+const LIST_ROW_TEXT_MARGIN: f32 = 4.0;
+const LIST_WHEEL_STEP: f32 = 40.0;
+const LIST_SELECTED_COLOR: Color = Color::SKYBLUE;
+const LIST_HOOVERED_COLOR: Color = Color::LIGHTGRAY;
+
+impl ListViewPar {
+    pub fn new() -> Self {
+        let list_view_par = Self {
+            widget_data: WidgetData::new(),
+            item_count: Cell::new(0),
+            row_builder: RefCell::new(None),
+            row_height: RefCell::new(RowHeight::Fixed(LIST_DEFAULT_ROW_HEIGHT)),
+            row_offsets: RefCell::new(Vec::new()),
+            row_offsets_valid: Cell::new(false),
+            visible_rows: RefCell::new(Vec::new()),
+            hoovered_row: Cell::new(None),
+            selection: RefCell::new(Selection::new(SelectionMode::Single)),
+            scroll_bar: ScrollBar::new(Vertical),
+        };
+        list_view_par.set_hooverable(true);
+        list_view_par
+    }
+
+    pub fn item_count(&self) -> usize {
+        self.item_count.get()
+    }
+
+    pub fn set_item_count(&self, gui: &Gui, item_count: usize) -> &ListViewPar {
+        if item_count == self.item_count.get() {
+            return self;
+        }
+        self.item_count.set(item_count);
+        self.row_offsets_valid.set(false);
+        if self.selection.borrow_mut().truncate(item_count) {
+            self.fire_selection_event(gui);
+        }
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn set_row_builder(&self, gui: &Gui, row_builder: impl Fn(usize) -> String + 'static) -> &ListViewPar {
+        self.row_builder.replace(Some(Box::new(row_builder)));
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn set_row_height(&self, gui: &Gui, row_height: RowHeight) -> &ListViewPar {
+        self.row_height.replace(row_height);
+        self.row_offsets_valid.set(false);
+        self.invalidate_position(gui);
+        self
+    }
+
+    /// Rebuild the visible rows, to use when the displayed items changed.
+    /// With variable row heights, the heights are queried again.
+    pub fn refresh(&self, gui: &Gui) -> &ListViewPar {
+        self.row_offsets_valid.set(false);
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn selection_mode(&self) -> SelectionMode {
+        self.selection.borrow().mode()
+    }
+
+    pub fn set_selection_mode(&self, gui: &Gui, selection_mode: SelectionMode) -> &ListViewPar {
+        if self.selection.borrow_mut().set_mode(selection_mode) {
+            self.fire_selection_event(gui);
+        }
+        self
+    }
+
+    pub fn selected_items(&self) -> Vec<usize> {
+        self.selection.borrow().selected()
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selection.borrow().is_selected(index)
+    }
+
+    pub fn select_item(&self, gui: &Gui, index: usize) -> &ListViewPar {
+        if index < self.item_count.get() && self.selection.borrow_mut().select(index) {
+            self.fire_selection_event(gui);
+        }
+        self
+    }
+
+    pub fn clear_selection(&self, gui: &Gui) -> &ListViewPar {
+        if self.selection.borrow_mut().clear() {
+            self.fire_selection_event(gui);
+        }
+        self
+    }
+
+    pub fn scroll_position(&self) -> f32 {
+        self.scroll_bar.value()
+    }
+
+    /// Scroll the list so that the item at `index` is visible
+    pub fn scroll_to_item(&self, gui: &Gui, index: usize) -> &ListViewPar {
+        if index >= self.item_count.get() {
+            return self;
+        }
+        self.ensure_row_offsets();
+        let top = self.row_top(index);
+        if self.scroll_bar.scroll_to_show(top, top + self.row_height_of(index)) {
+            self.invalidate_position(gui);
+        }
+        self
+    }
+
+    fn fire_selection_event(&self, gui: &Gui) {
+        if let Some(action_id) = self.action_id() {
+            gui.add_event(Select(SelectPar::new(&action_id, self.selected_items())))
+        }
+    }
+
+    fn ensure_row_offsets(&self) {
+        if self.row_offsets_valid.get() {
+            return;
+        }
+        let mut row_offsets = self.row_offsets.borrow_mut();
+        row_offsets.clear();
+        if let RowHeight::Variable(height_of) = self.row_height.borrow().deref() {
+            let mut offset = 0.0;
+            row_offsets.push(offset);
+            for index in 0..self.item_count.get() {
+                offset += height_of(index).max(0.0);
+                row_offsets.push(offset);
+            }
+        }
+        self.row_offsets_valid.set(true);
+    }
+
+    fn fixed_row_height(height: f32) -> f32 {
+        height.max(1.0)
+    }
+
+    fn row_top(&self, index: usize) -> f32 {
+        match self.row_height.borrow().deref() {
+            RowHeight::Fixed(height) => index as f32 * ListViewPar::fixed_row_height(*height),
+            RowHeight::Variable(_) => self.row_offsets.borrow()[index]
+        }
+    }
+
+    fn row_height_of(&self, index: usize) -> f32 {
+        match self.row_height.borrow().deref() {
+            RowHeight::Fixed(height) => ListViewPar::fixed_row_height(*height),
+            RowHeight::Variable(_) => {
+                let row_offsets = self.row_offsets.borrow();
+                row_offsets[index + 1] - row_offsets[index]
+            }
+        }
+    }
+
+    fn total_height(&self) -> f32 {
+        self.row_top(self.item_count.get())
+    }
+
+    /// index of the row at the provided vertical position (relative to the top of the first row)
+    fn row_at(&self, y: f32) -> usize {
+        let y = y.max(0.0);
+        match self.row_height.borrow().deref() {
+            RowHeight::Fixed(height) => (y / ListViewPar::fixed_row_height(*height)) as usize,
+            RowHeight::Variable(_) => {
+                let row_offsets = self.row_offsets.borrow();
+                row_offsets.partition_point(|offset| *offset <= y).max(1) - 1
+            }
+        }
+    }
+
+    fn measure_row(&self, text: &str) -> Size {
+        match self.text_style() {
+            None => Size::empty(),
+            Some(ts) => ts.measure_text(text)
+        }
+    }
+
+    fn update_visible_rows(&self, rows_width: f32) {
+        let content_layout = self.content_layout();
+        let scroll = self.scroll_bar.value();
+        let item_count = self.item_count.get();
+
+        let mut visible_rows = Vec::new();
+        if let Some(row_builder) = self.row_builder.borrow().as_ref() {
+            let mut index = self.row_at(scroll);
+            while index < item_count {
+                let top = self.row_top(index) - scroll;
+                if top >= content_layout.height {
+                    break;
+                }
+                let text = row_builder(index);
+                let text_size = self.measure_row(&text);
+                let layout = Rectangle::new(content_layout.x, content_layout.y + top, rows_width, self.row_height_of(index));
+                visible_rows.push(VisibleRow { index, text, text_size, layout });
+                index += 1;
+            }
+        }
+        self.visible_rows.replace(visible_rows);
+    }
+
+    fn visible_row_at(&self, offset: &Vector2, mouse_position: &Vector2) -> Option<usize> {
+        let mut content_layout = self.content_layout();
+        content_layout.x += offset.x;
+        content_layout.y += offset.y;
+        if !content_layout.check_collision_point_rec(mouse_position) {
+            return None;
+        }
+
+        self.visible_rows.borrow().iter().find(|row| {
+            let mut layout = row.layout;
+            layout.x += offset.x;
+            layout.y += offset.y;
+            layout.check_collision_point_rec(mouse_position)
+        }).map(|row| row.index)
+    }
+}
+
+impl WidgetSpecific for ListViewPar {
+
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, _gui: &Gui) -> Size {
+        let default_size = Size::new(LIST_DEFAULT_WIDTH, LIST_DEFAULT_HEIGHT).with_padding(&self.padding());
+        let mut preferred = self.preferred_size();
+        preferred.replace_empty_dimensions(&default_size);
+        preferred
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {
+        self.ensure_row_offsets();
+
+        let content_layout = self.content_layout();
+        self.scroll_bar.set_lengths(self.total_height(), content_layout.height);
+
+        let rows_width = if self.scroll_bar.is_needed() {
+            let rows_width = (content_layout.width - SCROLL_BAR_THICKNESS).max(0.0);
+            self.scroll_bar.set_layout(Rectangle::new(content_layout.x + rows_width, content_layout.y, SCROLL_BAR_THICKNESS, content_layout.height));
+            rows_width
+        } else {
+            content_layout.width
+        };
+
+        self.update_visible_rows(rows_width);
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        let mut scrolled = self.scroll_bar.update_action(offset, mouse_state);
+
+        let hoovered = self.get_hoover_state();
+        let wheel_move = mouse_state.wheel_move();
+        if hoovered && wheel_move != 0.0 {
+            scrolled |= self.scroll_bar.scroll_by(-wheel_move * LIST_WHEEL_STEP);
+        }
+
+        if scrolled {
+            self.invalidate_position(gui);
+        }
+
+        let hoovered_row = if hoovered && !self.scroll_bar.is_dragging() {
+            self.visible_row_at(offset, mouse_state.mouse_position())
+        } else {
+            None
+        };
+        self.hoovered_row.set(hoovered_row);
+
+        if mouse_state.left().is_pressed() {
+            if let Some(index) = hoovered_row {
+                if self.selection.borrow_mut().click(index) {
+                    self.fire_selection_event(gui);
+                }
+            }
+        }
+
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let mut content_layout = self.content_layout();
+        content_layout.x += offset.x;
+        content_layout.y += offset.y;
+
+        gui.push_clip(&content_layout);
+
+        let selection = self.selection.borrow();
+        let hoovered_row = self.hoovered_row.get();
+        let text_style = self.text_style();
+
+        for row in self.visible_rows.borrow().iter() {
+            let mut layout = row.layout;
+            layout.x += offset.x;
+            layout.y += offset.y;
+
+            if selection.is_selected(row.index) {
+                d.draw_rectangle_rec(layout, LIST_SELECTED_COLOR);
+            } else if hoovered_row == Some(row.index) {
+                d.draw_rectangle_rec(layout, LIST_HOOVERED_COLOR);
+            }
+
+            if let Some(ts) = text_style.as_ref() {
+                let position = Vector2::new(layout.x + LIST_ROW_TEXT_MARGIN, layout.y + (layout.height - row.text_size.height()) * 0.5);
+                ts.draw_text(d, &row.text, &position);
+            }
+        }
+
+        self.scroll_bar.render(d, offset);
+
+        gui.pop_clip();
+    }
+
+}
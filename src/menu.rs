@@ -0,0 +1,363 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use generational_arena::Index;
+use raylib::prelude::*;
+
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::text_style::TextStyle;
+
+const MENU_MARGIN: f32 = 6.0;
+const MENU_CHECK_WIDTH: f32 = 16.0;
+const MENU_ARROW_WIDTH: f32 = 12.0;
+const MENU_SHORTCUT_SPACING: f32 = 24.0;
+const MENU_SEPARATOR_HEIGHT: f32 = 7.0;
+const MENU_MIN_ITEM_HEIGHT: f32 = 20.0;
+const MENU_MIN_WIDTH: f32 = 80.0;
+const MENU_BACKGROUND_COLOR: Color = Color::RAYWHITE;
+const MENU_BORDER_COLOR: Color = Color::GRAY;
+const MENU_HOOVERED_COLOR: Color = Color::SKYBLUE;
+const MENU_DISABLED_COLOR: Color = Color::GRAY;
+const MENU_MARK_COLOR: Color = Color::DARKGRAY;
+
+enum MenuItemKind {
+    Action { id: String },
+    Submenu { menu: Rc<Menu> },
+    Separator,
+}
+
+/// An entry of a menu : an action identified by its id, a submenu or a separator
+pub struct MenuItem {
+    kind: MenuItemKind,
+    text: String,
+    shortcut: Option<String>,
+    enabled: bool,
+    /// None if the item is not checkable
+    checked: Option<Cell<bool>>,
+}
+
+impl MenuItem {
+    /// An item emitting a `MenuActivated` event with the provided id when activated
+    pub fn action(id: &str, text: &str) -> Self {
+        Self { kind: MenuItemKind::Action { id: id.to_string() }, text: text.to_string(), shortcut: None, enabled: true, checked: None }
+    }
+
+    pub fn submenu(text: &str, menu: Menu) -> Self {
+        Self { kind: MenuItemKind::Submenu { menu: Rc::new(menu) }, text: text.to_string(), shortcut: None, enabled: true, checked: None }
+    }
+
+    pub fn separator() -> Self {
+        Self { kind: MenuItemKind::Separator, text: "".to_string(), shortcut: None, enabled: false, checked: None }
+    }
+
+    /// The shortcut is only a hint displayed on the right of the item
+    pub fn with_shortcut(mut self, shortcut: &str) -> Self {
+        self.shortcut = Some(shortcut.to_string());
+        self
+    }
+
+    /// Make the item checkable, its state is toggled each time it is activated
+    pub fn with_check(mut self, checked: bool) -> Self {
+        self.checked = Some(Cell::new(checked));
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        match &self.kind {
+            MenuItemKind::Action { id } => Some(id),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn shortcut(&self) -> Option<&str> {
+        self.shortcut.as_deref()
+    }
+
+    pub fn is_separator(&self) -> bool {
+        matches!(self.kind, MenuItemKind::Separator)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && !self.is_separator()
+    }
+
+    pub fn is_checkable(&self) -> bool {
+        self.checked.is_some()
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked.as_ref().is_some_and(|c| c.get())
+    }
+
+    pub fn set_checked(&self, checked: bool) {
+        if let Some(c) = &self.checked {
+            c.set(checked)
+        }
+    }
+
+    fn submenu_model(&self) -> Option<&Rc<Menu>> {
+        match &self.kind {
+            MenuItemKind::Submenu { menu } => Some(menu),
+            _ => None,
+        }
+    }
+}
+
+/// The model of a menu, used by the menu bar and for the context menus
+pub struct Menu {
+    items: Vec<MenuItem>,
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    pub fn with_item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    pub fn add_item(&mut self, item: MenuItem) {
+        self.items.push(item);
+    }
+
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    /// Search the action with the provided id in the menu and its submenus
+    pub fn find_item(&self, id: &str) -> Option<&MenuItem> {
+        self.items.iter().find_map(|item| match &item.kind {
+            MenuItemKind::Action { id: item_id } if item_id == id => Some(item),
+            MenuItemKind::Submenu { menu } => menu.find_item(id),
+            _ => None,
+        })
+    }
+}
+
+/// What the open menu did with the mouse actions of the frame
+pub(crate) enum MenuResponse {
+    /// the actions are not for the menu and must be dispatched to the widgets
+    Ignored,
+    Consumed,
+    Close { consumed: bool },
+    Activated { id: String, checked: bool },
+}
+
+/// One menu of the chain of open menus
+struct MenuLevel {
+    menu: Rc<Menu>,
+    /// absolute coordinates
+    layout: Rectangle,
+    item_layouts: Vec<Rectangle>,
+    text_width: f32,
+    hoovered_item: Option<usize>,
+}
+
+impl MenuLevel {
+    fn new(menu: Rc<Menu>, position: Vector2, text_style: &Option<Rc<TextStyle>>, bounds: &Size) -> Self {
+        let measure = |text: &str| match text_style {
+            None => Size::empty(),
+            Some(ts) => ts.measure_text(text),
+        };
+
+        let mut text_width: f32 = 0.0;
+        let mut shortcut_width: f32 = 0.0;
+        let mut item_height = MENU_MIN_ITEM_HEIGHT;
+        for item in menu.items() {
+            let text_size = measure(item.text());
+            text_width = text_width.max(text_size.width());
+            item_height = item_height.max(text_size.height() + MENU_MARGIN);
+            if let Some(shortcut) = item.shortcut() {
+                shortcut_width = shortcut_width.max(measure(shortcut).width() + MENU_SHORTCUT_SPACING);
+            }
+        }
+
+        let width = (MENU_CHECK_WIDTH + text_width + shortcut_width + MENU_ARROW_WIDTH + 2.0 * MENU_MARGIN).max(MENU_MIN_WIDTH);
+        let heights: Vec<f32> = menu.items().iter()
+            .map(|item| if item.is_separator() { MENU_SEPARATOR_HEIGHT } else { item_height })
+            .collect();
+        let height: f32 = heights.iter().sum::<f32>() + MENU_MARGIN;
+
+        // keep the menu inside the bounds when possible
+        let x = position.x.min(bounds.width() - width).max(0.0);
+        let y = position.y.min(bounds.height() - height).max(0.0);
+        let layout = Rectangle::new(x, y, width, height);
+
+        let mut item_y = y + MENU_MARGIN * 0.5;
+        let item_layouts = heights.iter().map(|height| {
+            let item_layout = Rectangle::new(x, item_y, width, *height);
+            item_y += height;
+            item_layout
+        }).collect();
+
+        Self { menu, layout, item_layouts, text_width, hoovered_item: None }
+    }
+
+    fn item_at(&self, point: &Vector2) -> Option<usize> {
+        self.item_layouts.iter().position(|layout| layout.check_collision_point_rec(point))
+    }
+
+    fn render(&self, d: &mut impl RaylibDraw, text_style: &Option<Rc<TextStyle>>) {
+        d.draw_rectangle_rec(self.layout, MENU_BACKGROUND_COLOR);
+        d.draw_rectangle_lines_ex(self.layout, 1, MENU_BORDER_COLOR);
+
+        for (index, (item, layout)) in self.menu.items().iter().zip(self.item_layouts.iter()).enumerate() {
+            if item.is_separator() {
+                let y = layout.y + layout.height * 0.5;
+                d.draw_line_v(Vector2::new(layout.x + MENU_MARGIN, y), Vector2::new(layout.x + layout.width - MENU_MARGIN, y), MENU_BORDER_COLOR);
+                continue;
+            }
+
+            if self.hoovered_item == Some(index) && item.is_enabled() {
+                d.draw_rectangle_rec(*layout, MENU_HOOVERED_COLOR);
+            }
+
+            let center_y = layout.y + layout.height * 0.5;
+            if item.is_checked() {
+                let x = layout.x + MENU_MARGIN;
+                d.draw_line_v(Vector2::new(x + 2.0, center_y), Vector2::new(x + 5.0, center_y + 4.0), MENU_MARK_COLOR);
+                d.draw_line_v(Vector2::new(x + 5.0, center_y + 4.0), Vector2::new(x + 11.0, center_y - 4.0), MENU_MARK_COLOR);
+            }
+
+            if let Some(ts) = text_style {
+                let color = if item.is_enabled() { *ts.color() } else { MENU_DISABLED_COLOR };
+                let text_size = ts.measure_text(item.text());
+                let position = Vector2::new(layout.x + MENU_MARGIN + MENU_CHECK_WIDTH, center_y - text_size.height() * 0.5);
+                ts.draw_text_with_color(d, item.text(), &position, color);
+
+                if let Some(shortcut) = item.shortcut() {
+                    let position = Vector2::new(position.x + self.text_width + MENU_SHORTCUT_SPACING, position.y);
+                    ts.draw_text_with_color(d, shortcut, &position, MENU_DISABLED_COLOR);
+                }
+            }
+
+            if item.submenu_model().is_some() {
+                let x = layout.x + layout.width - MENU_MARGIN - MENU_ARROW_WIDTH * 0.5;
+                d.draw_triangle(
+                    Vector2::new(x - 3.0, center_y - 4.0),
+                    Vector2::new(x - 3.0, center_y + 4.0),
+                    Vector2::new(x + 3.0, center_y),
+                    MENU_MARK_COLOR,
+                );
+            }
+        }
+    }
+}
+
+/// The chain of menus currently open (the root menu and the open submenus), rendered above the widgets
+pub(crate) struct MenuPopup {
+    owner: Option<Index>,
+    text_style: Option<Rc<TextStyle>>,
+    levels: Vec<MenuLevel>,
+}
+
+impl MenuPopup {
+    pub(crate) fn new(menu: Rc<Menu>, position: Vector2, text_style: Option<Rc<TextStyle>>, owner: Option<Index>, bounds: &Size) -> Self {
+        let level = MenuLevel::new(menu, position, &text_style, bounds);
+        Self { owner, text_style, levels: vec![level] }
+    }
+
+    pub(crate) fn owner(&self) -> Option<Index> {
+        self.owner
+    }
+
+    pub(crate) fn root_menu(&self) -> &Rc<Menu> {
+        &self.levels[0].menu
+    }
+
+    pub(crate) fn contains(&self, point: &Vector2) -> bool {
+        self.levels.iter().any(|level| level.layout.check_collision_point_rec(point))
+    }
+
+    /// Update the hoovered items and open or close the submenus accordingly.
+    /// Returns true if the mouse is over one of the open menus
+    pub(crate) fn update_hoovered(&mut self, mouse_position: &Vector2, bounds: &Size) -> bool {
+        // the deepest menu under the mouse gets the hoover
+        let level_index = match self.levels.iter().rposition(|level| level.layout.check_collision_point_rec(mouse_position)) {
+            None => {
+                if let Some(level) = self.levels.last_mut() {
+                    level.hoovered_item = None;
+                }
+                return false;
+            }
+            Some(level_index) => level_index,
+        };
+
+        let submenu_open = self.levels.len() > level_index + 1;
+        let level = &mut self.levels[level_index];
+        let hoovered_item = level.item_at(mouse_position);
+        if hoovered_item == level.hoovered_item && submenu_open {
+            return true;
+        }
+        level.hoovered_item = hoovered_item;
+
+        let submenu = hoovered_item
+            .map(|index| &level.menu.items()[index])
+            .filter(|item| item.is_enabled())
+            .and_then(|item| item.submenu_model().cloned());
+        let item_layout = hoovered_item.map(|index| level.item_layouts[index]);
+        let parent_layout = level.layout;
+
+        self.levels.truncate(level_index + 1);
+        if let (Some(submenu), Some(item_layout)) = (submenu, item_layout) {
+            let mut sublevel = MenuLevel::new(submenu, Vector2::new(parent_layout.x + parent_layout.width, item_layout.y), &self.text_style, bounds);
+            // open on the left side when there is no room on the right
+            if sublevel.layout.x < parent_layout.x + parent_layout.width {
+                let shift = (parent_layout.x - sublevel.layout.width).max(0.0) - sublevel.layout.x;
+                sublevel.layout.x += shift;
+                sublevel.item_layouts.iter_mut().for_each(|layout| layout.x += shift);
+            }
+            self.levels.push(sublevel);
+        }
+        true
+    }
+
+    pub(crate) fn update_action(&self, mouse_state: &MouseState, owner_hoovered: bool) -> MenuResponse {
+        let mouse_position = mouse_state.mouse_position();
+
+        if !self.contains(mouse_position) {
+            if mouse_state.left().is_pressed() {
+                // the owner decides what a click on it does with its menu
+                return if owner_hoovered { MenuResponse::Ignored } else { MenuResponse::Close { consumed: true } };
+            }
+            if mouse_state.right().is_pressed() {
+                return MenuResponse::Close { consumed: false };
+            }
+            return MenuResponse::Ignored;
+        }
+
+        if mouse_state.left().is_released() {
+            let item = self.levels.iter().rev()
+                .find(|level| level.layout.check_collision_point_rec(mouse_position))
+                .and_then(|level| level.item_at(mouse_position).map(|index| &level.menu.items()[index]));
+
+            if let Some(item) = item.filter(|item| item.is_enabled()) {
+                if let Some(id) = item.id() {
+                    if item.is_checkable() {
+                        item.set_checked(!item.is_checked());
+                    }
+                    return MenuResponse::Activated { id: id.to_string(), checked: item.is_checked() };
+                }
+            }
+        }
+        MenuResponse::Consumed
+    }
+
+    pub(crate) fn render(&self, d: &mut impl RaylibDraw) {
+        for level in self.levels.iter() {
+            level.render(d, &self.text_style);
+        }
+    }
+}
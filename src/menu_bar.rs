@@ -0,0 +1,170 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+use std::rc::Rc;
+
+use raylib::prelude::*;
+
+use crate::gui::Gui;
+use crate::menu::Menu;
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+const MENU_BAR_MARGIN: f32 = 8.0;
+const MENU_BAR_OPEN_COLOR: Color = Color::SKYBLUE;
+const MENU_BAR_HOOVERED_COLOR: Color = Color::LIGHTGRAY;
+
+struct MenuBarEntry {
+    title: String,
+    menu: Rc<Menu>,
+    title_size: Size,
+    /// the layout of the title, in the same coordinates as the content layout
+    layout: Rectangle,
+}
+
+/// A horizontal bar of menu titles. Pressing a title opens its menu below the bar,
+/// and while a menu of the bar is open hoovering another title opens its menu instead
+pub struct MenuBarPar {
+    widget_data: WidgetData,
+    entries: RefCell<Vec<MenuBarEntry>>,
+    hoovered_entry: Cell<Option<usize>>,
+}
+
+impl Deref for MenuBarPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl MenuBarPar {
+    pub fn new() -> Self {
+        let par = Self {
+            widget_data: WidgetData::new(),
+            entries: RefCell::new(Vec::new()),
+            hoovered_entry: Cell::new(None),
+        };
+        par.set_hooverable(true);
+        par
+    }
+
+    pub fn add_menu(&self, gui: &Gui, title: &str, menu: Menu) -> &MenuBarPar {
+        self.entries.borrow_mut().push(MenuBarEntry {
+            title: title.to_string(),
+            menu: Rc::new(menu),
+            title_size: Size::empty(),
+            layout: Rectangle::default(),
+        });
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// The model of the menu at the provided position in the bar
+    pub fn menu(&self, index: usize) -> Option<Rc<Menu>> {
+        self.entries.borrow().get(index).map(|entry| entry.menu.clone())
+    }
+
+    fn entry_at(&self, offset: &Vector2, point: &Vector2) -> Option<usize> {
+        let local = Vector2::new(point.x - offset.x, point.y - offset.y);
+        self.entries.borrow().iter().position(|entry| entry.layout.check_collision_point_rec(local))
+    }
+
+    fn is_open_by_me(&self, gui: &Gui) -> bool {
+        gui.is_menu_open() && gui.menu_owner() == self.get_tree_index()
+    }
+
+    fn open_entry(&self, gui: &Gui, offset: &Vector2, index: usize) {
+        let widget_layout = self.widget_layout();
+        let entries = self.entries.borrow();
+        let entry = &entries[index];
+        let position = Vector2::new(entry.layout.x + offset.x, widget_layout.y + widget_layout.height + offset.y);
+        gui.open_menu(entry.menu.clone(), position, self.text_style(), self.get_tree_index());
+    }
+}
+
+impl WidgetSpecific for MenuBarPar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, _gui: &Gui) -> Size {
+        let text_style = self.text_style();
+        let mut size = Size::empty();
+        for entry in self.entries.borrow_mut().iter_mut() {
+            entry.title_size = match &text_style {
+                None => Size::empty(),
+                Some(ts) => ts.measure_text(&entry.title),
+            };
+            size.set_width(size.width() + entry.title_size.width() + 2.0 * MENU_BAR_MARGIN);
+            size.max_height_mut(&entry.title_size);
+        }
+        size.set_height(size.height() + 2.0 * MENU_BAR_MARGIN);
+
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&size.with_padding(&self.padding()));
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {
+        let content_layout = self.content_layout();
+        let mut x = content_layout.x;
+        for entry in self.entries.borrow_mut().iter_mut() {
+            let width = entry.title_size.width() + 2.0 * MENU_BAR_MARGIN;
+            entry.layout = Rectangle::new(x, content_layout.y, width, content_layout.height);
+            x += width;
+        }
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        let hoovered_entry = if self.get_hoover_state() {
+            self.entry_at(offset, mouse_state.mouse_position())
+        } else {
+            None
+        };
+        self.hoovered_entry.set(hoovered_entry);
+
+        if let Some(index) = hoovered_entry {
+            if mouse_state.left().is_pressed() {
+                if self.is_open_by_me(gui) {
+                    gui.close_menu();
+                } else {
+                    self.open_entry(gui, offset, index);
+                }
+            } else if self.is_open_by_me(gui) && !gui.is_menu_opened(&self.entries.borrow()[index].menu) {
+                self.open_entry(gui, offset, index);
+            }
+        }
+
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let text_style = self.text_style();
+        let hoovered_entry = self.hoovered_entry.get();
+
+        for (index, entry) in self.entries.borrow().iter().enumerate() {
+            let mut layout = entry.layout;
+            layout.x += offset.x;
+            layout.y += offset.y;
+
+            if gui.is_menu_opened(&entry.menu) {
+                d.draw_rectangle_rec(layout, MENU_BAR_OPEN_COLOR);
+            } else if hoovered_entry == Some(index) {
+                d.draw_rectangle_rec(layout, MENU_BAR_HOOVERED_COLOR);
+            }
+
+            if let Some(ts) = &text_style {
+                let position = Vector2::new(layout.x + MENU_BAR_MARGIN, layout.y + (layout.height - entry.title_size.height()) * 0.5);
+                ts.draw_text(d, &entry.title, &position);
+            }
+        }
+    }
+}
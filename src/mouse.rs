@@ -1,14 +1,25 @@
 use raylib::prelude::*;
 
+#[derive(Clone)]
 pub struct MouseState {
     left: MouseButtonState,
     middle: MouseButtonState,
     right: MouseButtonState,
     mouse_position: Vector2,
+    wheel_move: f32,
     drag_info: DragInfo,
+    /// the left button was pressed twice quickly at the same place
+    double_clicked: bool,
+    last_click_time: f64,
+    last_click_position: Vector2,
 }
 
-#[derive(Debug)]
+/// the maximal delay in seconds between the two presses of a double click
+const DOUBLE_CLICK_DELAY: f64 = 0.4;
+/// the maximal distance in pixels between the two presses of a double click
+const DOUBLE_CLICK_DISTANCE: f32 = 4.0;
+
+#[derive(Debug, Clone)]
 pub struct MouseButtonState {
     button:raylib::consts::MouseButton,
     ///the button is pushed
@@ -20,7 +31,7 @@ pub struct MouseButtonState {
     ///the button switch from down to up since the last frame
     released: bool,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DragInfo {
     in_progress:bool,
     started:bool,
@@ -35,10 +46,14 @@ impl MouseState {
     pub fn new() -> Self {
         Self {
             mouse_position:Vector2::default(),
+            wheel_move:0.0,
             right: MouseButtonState::new(raylib::consts::MouseButton::MOUSE_RIGHT_BUTTON),
             middle: MouseButtonState::new(raylib::consts::MouseButton::MOUSE_MIDDLE_BUTTON),
             left: MouseButtonState::new(raylib::consts::MouseButton::MOUSE_LEFT_BUTTON),
-            drag_info: Default::default()
+            drag_info: Default::default(),
+            double_clicked: false,
+            last_click_time: f64::NEG_INFINITY,
+            last_click_position: Vector2::default(),
         }
     }
 
@@ -48,8 +63,10 @@ impl MouseState {
         self.right.update(d);
 
         self.mouse_position = d.get_mouse_position();
+        self.wheel_move = d.get_mouse_wheel_move();
 
-        self.drag_info.update_drag_info(&self.left, &self.mouse_position)
+        self.drag_info.update_drag_info(&self.left, &self.mouse_position);
+        self.update_double_click(d.get_time())
     }
 
     pub fn update_2d(&mut self, d:&RaylibDrawHandle, camera:Camera2D) {
@@ -59,14 +76,43 @@ impl MouseState {
 
         let mouse_position = d.get_mouse_position();
         self.mouse_position = d.get_screen_to_world2D(mouse_position,camera);
+        self.wheel_move = d.get_mouse_wheel_move();
 
-        self.drag_info.update_drag_info(&self.left, &self.mouse_position)
+        self.drag_info.update_drag_info(&self.left, &self.mouse_position);
+        self.update_double_click(d.get_time())
     }
 
     pub fn mouse_position(&self) -> &Vector2 {
         &self.mouse_position
     }
 
+    /// detect the second press of a double click
+    fn update_double_click(&mut self, time: f64) {
+        self.double_clicked = false;
+        if !self.left.is_pressed() {
+            return;
+        }
+        let distance = self.mouse_position.distance_to(self.last_click_position);
+        if time - self.last_click_time <= DOUBLE_CLICK_DELAY && distance <= DOUBLE_CLICK_DISTANCE {
+            self.double_clicked = true;
+            // a third press starts a new double click
+            self.last_click_time = f64::NEG_INFINITY;
+        } else {
+            self.last_click_time = time;
+        }
+        self.last_click_position = self.mouse_position;
+    }
+
+    /// true on the second press of a double click with the left button
+    pub fn is_double_clicked(&self) -> bool {
+        self.double_clicked
+    }
+
+    /// the amount the mouse wheel moved since the last frame
+    pub fn wheel_move(&self) -> f32 {
+        self.wheel_move
+    }
+
     pub fn left(&self) -> &MouseButtonState {
         &self.left
     }
@@ -79,6 +125,23 @@ impl MouseState {
     pub fn drag_info(&self) -> &DragInfo {
         &self.drag_info
     }
+
+    /// true when a button went down since the last frame
+    pub(crate) fn is_any_pressed(&self) -> bool {
+        self.left.pressed || self.middle.pressed || self.right.pressed
+    }
+
+    /// The same state without the presses of this frame, for the widgets a press cannot reach.
+    /// The drags already in progress go on
+    pub(crate) fn without_presses(&self) -> MouseState {
+        let mut mouse_state = self.clone();
+        mouse_state.left.pressed = false;
+        mouse_state.middle.pressed = false;
+        mouse_state.right.pressed = false;
+        mouse_state.double_clicked = false;
+        mouse_state.drag_info.started = false;
+        mouse_state
+    }
 }
 
 impl Default for DragInfo {
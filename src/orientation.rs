@@ -0,0 +1,18 @@
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+impl Orientation {
+
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self,Orientation::Horizontal)
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        !self.is_horizontal()
+    }
+
+}
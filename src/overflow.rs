@@ -0,0 +1,14 @@
+/// What happens to the part of the children outside the content of their parent
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Overflow {
+    /// the children are rendered and hit tested everywhere
+    Visible,
+    /// the children are rendered and hit tested only inside the content layout of the parent
+    Clip,
+}
+
+impl Overflow {
+    pub fn is_clipped(&self) -> bool {
+        matches!(self, Overflow::Clip)
+    }
+}
@@ -0,0 +1,240 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+
+use raylib::prelude::*;
+
+use crate::background::BackgroundRenderer;
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::orientation::Orientation;
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+const PROGRESS_BAR_LENGTH: f32 = 200.0;
+const PROGRESS_BAR_THICKNESS: f32 = 20.0;
+const PROGRESS_BAR_COLOR: Color = Color::GREEN;
+/// the length of the moving block in indeterminate mode, relatively to the bar length
+const PROGRESS_INDETERMINATE_BLOCK: f32 = 0.25;
+/// the time in seconds for the moving block to go from one end to the other
+const PROGRESS_INDETERMINATE_PERIOD: f32 = 1.0;
+
+/// A bar showing the progress of a task, or an animation when the progress is unknown
+/// (indeterminate mode). The animation follows the time provided by `Gui::update_time`.
+/// The filled part is drawn with the background named by the bar style, if it exists
+pub struct ProgressBarPar {
+    widget_data: WidgetData,
+    value: Cell<f32>,
+    value_min: Cell<f32>,
+    value_max: Cell<f32>,
+    orientation: Cell<Orientation>,
+    indeterminate: Cell<bool>,
+    text_visible: Cell<bool>,
+    value_text: RefCell<String>,
+    value_text_size: Cell<Size>,
+    bar_style_name: RefCell<String>,
+}
+
+impl Deref for ProgressBarPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl ProgressBarPar {
+    pub fn new() -> Self {
+        Self {
+            widget_data: WidgetData::new(),
+            value: Cell::new(0.0),
+            value_min: Cell::new(0.0),
+            value_max: Cell::new(100.0),
+            orientation: Cell::new(Orientation::Horizontal),
+            indeterminate: Cell::new(false),
+            text_visible: Cell::new(false),
+            value_text: RefCell::new("".to_string()),
+            value_text_size: Cell::new(Size::empty()),
+            bar_style_name: RefCell::new("".to_string()),
+        }
+    }
+
+    pub fn get_value(&self) -> f32 {
+        self.value.get()
+    }
+
+    pub fn get_value_min(&self) -> f32 {
+        self.value_min.get()
+    }
+
+    pub fn get_value_max(&self) -> f32 {
+        self.value_max.get()
+    }
+
+    pub fn set_value(&self, gui: &Gui, value: f32) -> &ProgressBarPar {
+        self.value.set(value);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn set_value_min(&self, gui: &Gui, value: f32) -> &ProgressBarPar {
+        self.value_min.set(value);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn set_value_max(&self, gui: &Gui, value: f32) -> &ProgressBarPar {
+        self.value_max.set(value);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation.get()
+    }
+
+    pub fn set_orientation(&self, gui: &Gui, orientation: Orientation) -> &ProgressBarPar {
+        self.orientation.set(orientation);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn is_indeterminate(&self) -> bool {
+        self.indeterminate.get()
+    }
+
+    /// In indeterminate mode the value is ignored and a block moves back and forth along the bar
+    pub fn set_indeterminate(&self, gui: &Gui, indeterminate: bool) -> &ProgressBarPar {
+        self.indeterminate.set(indeterminate);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn is_text_visible(&self) -> bool {
+        self.text_visible.get()
+    }
+
+    /// Display the value over the bar (never in indeterminate mode)
+    pub fn set_text_visible(&self, gui: &Gui, text_visible: bool) -> &ProgressBarPar {
+        self.text_visible.set(text_visible);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn set_bar_style(&self, bar_style_name: &str) -> &ProgressBarPar {
+        self.bar_style_name.replace(bar_style_name.to_string());
+        self
+    }
+
+    fn format_value(&self, value: f32) -> String {
+        format!("{:5.1}", value)
+    }
+
+    fn measure_value(&self, formatted_value: &str) -> Size {
+        match self.text_style() {
+            None => Size::empty(),
+            Some(ts) => ts.measure_text(formatted_value)
+        }
+    }
+
+    fn shows_text(&self) -> bool {
+        self.text_visible.get() && !self.indeterminate.get()
+    }
+
+    /// The start and the end of the filled part along the bar, between 0 and 1
+    fn filled_range(&self, time: f64) -> (f32, f32) {
+        if self.indeterminate.get() {
+            let phase = ((time / PROGRESS_INDETERMINATE_PERIOD as f64) % 2.0) as f32;
+            let ping_pong = if phase > 1.0 { 2.0 - phase } else { phase };
+            let start = ping_pong * (1.0 - PROGRESS_INDETERMINATE_BLOCK);
+            return (start, start + PROGRESS_INDETERMINATE_BLOCK);
+        }
+
+        let value_min = self.value_min.get();
+        let value_max = self.value_max.get();
+        if value_max <= value_min {
+            return (0.0, 0.0);
+        }
+        (0.0, ((self.value.get() - value_min) / (value_max - value_min)).clamp(0.0, 1.0))
+    }
+}
+
+impl WidgetSpecific for ProgressBarPar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, _gui: &Gui) -> Size {
+        let text_size = if self.shows_text() {
+            let formatted_value = self.format_value(self.value.get());
+            let text_size = self.measure_value(&formatted_value);
+            self.value_text.replace(formatted_value);
+            text_size
+        } else {
+            Size::empty()
+        };
+        self.value_text_size.set(text_size);
+
+        let bar_size = match self.orientation.get() {
+            Orientation::Horizontal => Size::new(PROGRESS_BAR_LENGTH, PROGRESS_BAR_THICKNESS),
+            Orientation::Vertical => Size::new(PROGRESS_BAR_THICKNESS, PROGRESS_BAR_LENGTH),
+        };
+
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&text_size.max(&bar_size).with_padding(&self.padding()));
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {}
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let mut content_layout = self.content_layout();
+        content_layout.x += offset.x;
+        content_layout.y += offset.y;
+
+        let (start, end) = self.filled_range(gui.time());
+        let filled_layout = match self.orientation.get() {
+            Orientation::Horizontal => Rectangle::new(
+                content_layout.x + start * content_layout.width,
+                content_layout.y,
+                (end - start) * content_layout.width,
+                content_layout.height,
+            ),
+            // a vertical bar fills from the bottom
+            Orientation::Vertical => Rectangle::new(
+                content_layout.x,
+                content_layout.y + (1.0 - end) * content_layout.height,
+                content_layout.width,
+                (end - start) * content_layout.height,
+            ),
+        };
+
+        let bar_style = gui.get_background(&self.bar_style_name.borrow());
+        match bar_style {
+            None => d.draw_rectangle_rec(filled_layout, PROGRESS_BAR_COLOR),
+            Some(background) => background.draw(d, &filled_layout, false, false),
+        }
+
+        if self.shows_text() {
+            if let Some(ts) = self.text_style() {
+                let text_size = self.value_text_size.get();
+                let mut position = Vector2::new(content_layout.x, content_layout.y);
+                position.x += (content_layout.width - text_size.width()) * 0.5;
+                position.y += (content_layout.height - text_size.height()) * 0.5;
+
+                let borrowed_text = self.value_text.borrow();
+                ts.draw_text(d, borrowed_text.as_str(), &position);
+            }
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+
+use raylib::prelude::*;
+
+use crate::event::Event::RangeDrag;
+use crate::event::RangePar;
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+const RANGE_SLIDER_BAR_HEIGHT: f32 = 20.0;
+const RANGE_SLIDER_BAR_WIDTH: f32 = 100.0;
+const RANGE_SLIDER_BAR_COLOR: Color = Color::GRAY;
+const RANGE_SLIDER_SPAN_COLOR: Color = Color::SKYBLUE;
+
+const RANGE_SLIDER_CURSOR_SPACING: f32 = 2.0;
+const RANGE_SLIDER_CURSOR_WIDTH: f32 = 5.0;
+const RANGE_SLIDER_CURSOR_COLOR: Color = Color::GREEN;
+
+/// The part of the range slider being dragged
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum RangePart {
+    Low,
+    High,
+    /// the span between the thumbs, moving the whole range
+    Span,
+}
+
+/// A slider with two thumbs selecting an interval `[low, high]` between the minimum and the
+/// maximum values. The thumbs cannot cross each other and dragging the span between them moves
+/// the whole interval. It emits `RangeDrag` events while dragging, when done and when cancelled
+/// with a right click.
+pub struct RangeSliderPar {
+    widget_data: WidgetData,
+    value_min: Cell<f32>,
+    value_max: Cell<f32>,
+    low: Cell<f32>,
+    high: Cell<f32>,
+    step: Cell<Option<f32>>,
+    dragged_part: Cell<Option<RangePart>>,
+    drag_low: Cell<f32>,
+    drag_high: Cell<f32>,
+    drag_starting_position: Cell<Vector2>,
+    value_text: RefCell<String>,
+    value_text_size: Cell<Size>,
+    low_cursor_layout: Cell<Rectangle>,
+    high_cursor_layout: Cell<Rectangle>,
+}
+
+impl Deref for RangeSliderPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl RangeSliderPar {
+    pub fn new() -> Self {
+        Self {
+            widget_data: WidgetData::new(),
+            value_min: Cell::new(0.0),
+            value_max: Cell::new(100.0),
+            low: Cell::new(25.0),
+            high: Cell::new(75.0),
+            step: Cell::new(None),
+            dragged_part: Cell::new(None),
+            drag_low: Cell::new(25.0),
+            drag_high: Cell::new(75.0),
+            drag_starting_position: Cell::new(Vector2::default()),
+            value_text: RefCell::new("".to_string()),
+            value_text_size: Cell::new(Size::empty()),
+            low_cursor_layout: Cell::new(Rectangle::default()),
+            high_cursor_layout: Cell::new(Rectangle::default()),
+        }
+    }
+
+    pub fn get_low(&self) -> f32 {
+        self.low.get()
+    }
+
+    pub fn get_high(&self) -> f32 {
+        self.high.get()
+    }
+
+    pub fn get_value_min(&self) -> f32 {
+        self.value_min.get()
+    }
+
+    pub fn get_value_max(&self) -> f32 {
+        self.value_max.get()
+    }
+
+    /// Set the selected interval, swapping the bounds if needed and keeping them inside the limits
+    pub fn set_range(&self, gui: &Gui, low: f32, high: f32) -> &RangeSliderPar {
+        let (low, high) = if low <= high { (low, high) } else { (high, low) };
+        self.low.set(self.clamp(low));
+        self.high.set(self.clamp(high));
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn set_value_min(&self, gui: &Gui, value: f32) -> &RangeSliderPar {
+        self.value_min.set(value);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn set_value_max(&self, gui: &Gui, value: f32) -> &RangeSliderPar {
+        self.value_max.set(value);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// Snap the bounds to multiples of `step` from the minimum value, or leave them continuous with `None`
+    pub fn set_step(&self, gui: &Gui, step: Option<f32>) -> &RangeSliderPar {
+        self.step.set(step.filter(|step| *step > 0.0));
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    fn format_range(&self, low: f32, high: f32) -> String {
+        format!("{:.1} - {:.1}", low, high)
+    }
+
+    fn effective_range(&self) -> (f32, f32) {
+        if self.dragged_part.get().is_some() {
+            (self.drag_low.get(), self.drag_high.get())
+        } else {
+            (self.low.get(), self.high.get())
+        }
+    }
+
+    fn clamp(&self, value: f32) -> f32 {
+        let value_min = self.value_min.get();
+        let value_max = self.value_max.get();
+        value.clamp(value_min.min(value_max), value_min.max(value_max))
+    }
+
+    fn snap(&self, value: f32) -> f32 {
+        let value_min = self.value_min.get();
+        match self.step.get() {
+            None => self.clamp(value),
+            Some(step) => self.clamp(value_min + ((value - value_min) / step).round() * step),
+        }
+    }
+
+    fn value_to_fraction(&self, value: f32) -> f32 {
+        let value_min = self.value_min.get();
+        let value_max = self.value_max.get();
+        if value_max == value_min { 0.0 } else { ((value - value_min) / (value_max - value_min)).clamp(0.0, 1.0) }
+    }
+
+    /// The value under the point, the cursor being centered on the point
+    fn value_at(&self, offset: &Vector2, point: &Vector2) -> f32 {
+        let content_layout = self.content_layout();
+        let available_width = content_layout.width - RANGE_SLIDER_CURSOR_WIDTH;
+        if available_width <= 0.0 {
+            return self.value_min.get();
+        }
+        let fraction = (point.x - (content_layout.x + offset.x + RANGE_SLIDER_CURSOR_WIDTH * 0.5)) / available_width;
+        fraction.clamp(0.0, 1.0) * (self.value_max.get() - self.value_min.get()) + self.value_min.get()
+    }
+
+    fn cursor_layout(&self, value: f32) -> Rectangle {
+        let content_layout = self.content_layout();
+        let available_width = content_layout.width - RANGE_SLIDER_CURSOR_WIDTH;
+        Rectangle::new(
+            content_layout.x + available_width * self.value_to_fraction(value),
+            content_layout.y + RANGE_SLIDER_CURSOR_SPACING,
+            RANGE_SLIDER_CURSOR_WIDTH,
+            content_layout.height - 2.0 * RANGE_SLIDER_CURSOR_SPACING,
+        )
+    }
+
+    /// The part under the point. When the thumbs overlap the one to pick is the one that can
+    /// move toward the point
+    fn part_at(&self, offset: &Vector2, point: &Vector2) -> Option<RangePart> {
+        let local = Vector2::new(point.x - offset.x, point.y - offset.y);
+        let low_layout = self.low_cursor_layout.get();
+        let high_layout = self.high_cursor_layout.get();
+        let on_low = low_layout.check_collision_point_rec(local);
+        let on_high = high_layout.check_collision_point_rec(local);
+
+        match (on_low, on_high) {
+            (true, true) => {
+                if local.x < low_layout.x + low_layout.width * 0.5 { Some(RangePart::Low) } else { Some(RangePart::High) }
+            }
+            (true, false) => Some(RangePart::Low),
+            (false, true) => Some(RangePart::High),
+            (false, false) => {
+                let content_layout = self.content_layout();
+                if !content_layout.check_collision_point_rec(local) {
+                    None
+                } else if local.x < low_layout.x {
+                    Some(RangePart::Low)
+                } else if local.x > high_layout.x + high_layout.width {
+                    Some(RangePart::High)
+                } else {
+                    Some(RangePart::Span)
+                }
+            }
+        }
+    }
+
+    /// Move the dragged part to follow the mouse. Returns true if the range changed
+    fn drag_to(&self, part: RangePart, offset: &Vector2, point: &Vector2) -> bool {
+        let (low, high) = (self.drag_low.get(), self.drag_high.get());
+        let (new_low, new_high) = match part {
+            RangePart::Low => (self.snap(self.value_at(offset, point)).min(high), high),
+            RangePart::High => (low, self.snap(self.value_at(offset, point)).max(low)),
+            RangePart::Span => {
+                let start_value = self.value_at(offset, &self.drag_starting_position.get());
+                let delta = self.value_at(offset, point) - start_value;
+                let (start_low, start_high) = (self.low.get(), self.high.get());
+                // keep the width of the range when it hits a limit
+                let value_min = self.value_min.get().min(self.value_max.get());
+                let value_max = self.value_min.get().max(self.value_max.get());
+                let delta = delta.clamp(value_min - start_low, value_max - start_high);
+                let new_low = self.snap(start_low + delta).min(value_max - (start_high - start_low));
+                (new_low, new_low + (start_high - start_low))
+            }
+        };
+        if (new_low, new_high) == (low, high) {
+            return false;
+        }
+        self.drag_low.set(new_low);
+        self.drag_high.set(new_high);
+        true
+    }
+
+    fn fire_range_event(&self, gui: &Gui, event: fn(String, f32, f32) -> RangePar, low: f32, high: f32) {
+        if let Some(action_id) = self.action_id() {
+            gui.add_event(RangeDrag(event(action_id, low, high)))
+        }
+    }
+}
+
+impl WidgetSpecific for RangeSliderPar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, _gui: &Gui) -> Size {
+        let (low, high) = self.effective_range();
+        let formatted_range = self.format_range(low, high);
+        let text_size = match self.text_style() {
+            None => Size::empty(),
+            Some(ts) => ts.measure_text(&formatted_range),
+        };
+        self.value_text.replace(formatted_range);
+        self.value_text_size.set(text_size);
+        let bar_size = Size::new(RANGE_SLIDER_BAR_WIDTH, RANGE_SLIDER_BAR_HEIGHT);
+
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&text_size.max(&bar_size).with_padding(&self.padding()));
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {
+        let (low, high) = self.effective_range();
+        self.low_cursor_layout.set(self.cursor_layout(low));
+        self.high_cursor_layout.set(self.cursor_layout(high));
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+
+        let drag_info = mouse_state.drag_info();
+
+        if mouse_state.right().is_pressed() && self.dragged_part.get().is_some() {
+            self.dragged_part.set(None);
+            self.invalidate_preferred_size(gui);
+            self.invalidate_position(gui);
+            self.fire_range_event(gui, RangePar::cancelled, self.low.get(), self.high.get());
+            return;
+        }
+
+        if drag_info.started() {
+            let mouse_position = *drag_info.starting_position();
+            if let Some(part) = self.part_at(offset, &mouse_position) {
+                self.drag_low.set(self.low.get());
+                self.drag_high.set(self.high.get());
+                self.dragged_part.set(Some(part));
+                self.drag_starting_position.set(mouse_position);
+                // a press on the bar outside of the thumbs brings the closest one under the mouse
+                if part != RangePart::Span && self.drag_to(part, offset, &mouse_position) {
+                    self.invalidate_preferred_size(gui);
+                    self.invalidate_position(gui);
+                    self.fire_range_event(gui, RangePar::in_progress, self.drag_low.get(), self.drag_high.get());
+                }
+            }
+        } else if drag_info.in_progress() {
+            if let Some(part) = self.dragged_part.get() {
+                if self.drag_to(part, offset, drag_info.current_position()) {
+                    self.invalidate_preferred_size(gui);
+                    self.invalidate_position(gui);
+                    self.fire_range_event(gui, RangePar::in_progress, self.drag_low.get(), self.drag_high.get());
+                }
+            }
+        } else if drag_info.done() && self.dragged_part.get().is_some() {
+            let (low, high) = (self.drag_low.get(), self.drag_high.get());
+            self.low.set(low);
+            self.high.set(high);
+            self.dragged_part.set(None);
+            self.invalidate_preferred_size(gui);
+            self.invalidate_position(gui);
+            self.fire_range_event(gui, RangePar::done, low, high);
+        }
+    }
+
+    fn render_my_visual(&self, _gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let mut content_layout = self.content_layout();
+        content_layout.x += offset.x;
+        content_layout.y += offset.y;
+
+        d.draw_rectangle_rec(content_layout, RANGE_SLIDER_BAR_COLOR);
+
+        let mut low_layout = self.low_cursor_layout.get();
+        low_layout.x += offset.x;
+        low_layout.y += offset.y;
+        let mut high_layout = self.high_cursor_layout.get();
+        high_layout.x += offset.x;
+        high_layout.y += offset.y;
+
+        let span_x = low_layout.x + low_layout.width;
+        let span_layout = Rectangle::new(span_x, low_layout.y, (high_layout.x - span_x).max(0.0), low_layout.height);
+        d.draw_rectangle_rec(span_layout, RANGE_SLIDER_SPAN_COLOR);
+
+        d.draw_rectangle_rec(low_layout, RANGE_SLIDER_CURSOR_COLOR);
+        d.draw_rectangle_rec(high_layout, RANGE_SLIDER_CURSOR_COLOR);
+
+        if let Some(ts) = self.text_style() {
+            let text_size = self.value_text_size.get();
+            let mut position = Vector2::new(content_layout.x, content_layout.y);
+            position.x += (content_layout.width - text_size.width()) * 0.5;
+            position.y += (content_layout.height - text_size.height()) * 0.5;
+            ts.draw_text(d, self.value_text.borrow().as_str(), &position);
+        }
+    }
+}
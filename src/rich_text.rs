@@ -0,0 +1,597 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+use std::rc::Rc;
+
+use raylib::prelude::*;
+
+use crate::alignment::HAlignment;
+use crate::color::parse_color;
+use crate::event::Event::LinkClicked;
+use crate::event::LinkPar;
+use crate::font::FontInfo;
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+/// the text style used by the `[b]` tag
+pub const RICH_TEXT_BOLD_STYLE: &str = "bold";
+/// the text style used by the `[i]` tag
+pub const RICH_TEXT_ITALIC_STYLE: &str = "italic";
+
+const RICH_TEXT_BORDER: f32 = 3.0;
+
+/// the color of the links without a color of their own
+pub(crate) const LINK_COLOR: Color = Color::BLUE;
+/// the color of the link under the mouse, which is also underlined
+pub(crate) const LINK_HOOVER_COLOR: Color = Color::DARKBLUE;
+
+/// What a span shows
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpanContent {
+    Text(String),
+    /// the name of an image loaded in the gui, scaled to the height of the font of the span
+    Icon(String),
+}
+
+/// A run of text drawn with one style, or an inline icon
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSpan {
+    content: SpanContent,
+    /// a named text style replacing the one of the widget
+    style_name: Option<String>,
+    color: Option<Color>,
+    font_size: Option<f32>,
+    /// the id sent by a `LinkClicked` event when the span is clicked
+    link: Option<String>,
+}
+
+impl TextSpan {
+    pub fn text(text: &str) -> Self {
+        Self { content: SpanContent::Text(text.to_string()), style_name: None, color: None, font_size: None, link: None }
+    }
+
+    pub fn icon(image_name: &str) -> Self {
+        Self { content: SpanContent::Icon(image_name.to_string()), style_name: None, color: None, font_size: None, link: None }
+    }
+
+    pub fn with_style(mut self, style_name: &str) -> Self {
+        self.style_name = Some(style_name.to_string());
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Make the span a link, clicking it emits a `LinkClicked` event with the id
+    pub fn with_link(mut self, link_id: &str) -> Self {
+        self.link = Some(link_id.to_string());
+        self
+    }
+
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    pub fn content(&self) -> &SpanContent {
+        &self.content
+    }
+}
+
+/// The place of each piece in lines of a given width
+struct PiecesArrangement {
+    positions: Vec<Vector2>,
+    lines: Vec<usize>,
+    line_widths: Vec<f32>,
+    size: Size,
+}
+
+/// A piece of a line : a word, the spaces between words or an icon
+struct RichPiece {
+    kind: PieceKind,
+    size: Size,
+    /// the distance from the top of the piece to its baseline
+    baseline: f32,
+    /// the position of the piece relative to the top left corner of the text
+    position: Vector2,
+    line: usize,
+    /// the index of the span of the piece when it is a link
+    link_span: Option<usize>,
+}
+
+enum PieceKind {
+    Word { text: String, font: FontInfo, color: Color, spacing: f32 },
+    Space,
+    Icon(Rc<Texture2D>),
+    LineBreak,
+}
+
+/// A text made of spans with different text styles, colors, sizes and inline icons, laid out
+/// and wrapped together with the spans of a line sharing the same baseline. The spans can be
+/// built with a small markup :
+/// `[b]bold[/b]`, `[i]italic[/i]`, `[style=name]...[/style]`, `[color=red]...[/color]`
+/// (see `color::parse_color`), `[size=24]...[/size]`, `[icon=name]` and `[link=id]...[/link]`
+/// making a link emitting `LinkClicked` events. `[[` stands for `[`
+pub struct RichTextPar {
+    widget_data: WidgetData,
+    spans: RefCell<Vec<TextSpan>>,
+    wrap: Cell<bool>,
+    text_alignment: Cell<HAlignment>,
+    /// the pieces built from the spans, placed for the width of the widget by the position step
+    pieces: RefCell<Vec<RichPiece>>,
+    line_widths: RefCell<Vec<f32>>,
+    text_size: Cell<Size>,
+    link_color: Cell<Color>,
+    link_hoover_color: Cell<Color>,
+    /// the span of the link under the mouse
+    hoovered_link: Cell<Option<usize>>,
+    /// the span of the link the press started on, clicked if the release happens on it too
+    armed_link: Cell<Option<usize>>,
+}
+
+impl Deref for RichTextPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl RichTextPar {
+    pub fn new() -> Self {
+        let par = Self {
+            widget_data: WidgetData::new(),
+            spans: RefCell::new(Vec::new()),
+            wrap: Cell::new(false),
+            text_alignment: Cell::new(HAlignment::Left),
+            pieces: RefCell::new(Vec::new()),
+            line_widths: RefCell::new(Vec::new()),
+            text_size: Cell::new(Size::empty()),
+            link_color: Cell::new(LINK_COLOR),
+            link_hoover_color: Cell::new(LINK_HOOVER_COLOR),
+            hoovered_link: Cell::new(None),
+            armed_link: Cell::new(None),
+        };
+        par.set_hooverable(true);
+        par
+    }
+
+    pub fn set_spans(&self, gui: &Gui, spans: Vec<TextSpan>) -> &RichTextPar {
+        self.spans.replace(spans);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// Build the spans from a text with markup tags
+    pub fn set_markup(&self, gui: &Gui, markup: &str) -> &RichTextPar {
+        self.set_spans(gui, parse_markup(markup))
+    }
+
+    /// Break the lines between words to fit the width of the widget
+    pub fn set_wrap(&self, gui: &Gui, wrap: bool) -> &RichTextPar {
+        self.wrap.set(wrap);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn set_text_alignment(&self, gui: &Gui, alignment: HAlignment) -> &RichTextPar {
+        self.text_alignment.set(alignment);
+        self.invalidate_position(gui);
+        self
+    }
+
+    /// The colors of the links without a color of their own, and of the link under the mouse
+    pub fn set_link_colors(&self, gui: &Gui, color: Color, hoover_color: Color) -> &RichTextPar {
+        self.link_color.set(color);
+        self.link_hoover_color.set(hoover_color);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// The layout of a piece, in the same coordinates as the content layout
+    fn piece_layout(&self, piece: &RichPiece) -> Rectangle {
+        let content_layout = self.content_layout();
+        let text_size = self.text_size.get();
+        let line_width = self.line_widths.borrow().get(piece.line).copied().unwrap_or(0.0);
+        let origin_x = match self.text_alignment.get() {
+            HAlignment::Left | HAlignment::Stretch => content_layout.x + RICH_TEXT_BORDER,
+            HAlignment::Middle => content_layout.x + (content_layout.width - line_width) * 0.5,
+            HAlignment::Right => content_layout.x + content_layout.width - RICH_TEXT_BORDER - line_width,
+        };
+        let origin_y = content_layout.y + (content_layout.height - text_size.height()) * 0.5;
+        Rectangle::new(origin_x + piece.position.x, origin_y + piece.position.y, piece.size.width(), piece.size.height())
+    }
+
+    /// Split the spans in pieces with their resolved font, color and size
+    fn build_pieces(&self, gui: &Gui) -> Vec<RichPiece> {
+        let default_style = self.text_style();
+        let mut pieces = Vec::new();
+
+        for (span_index, span) in self.spans.borrow().iter().enumerate() {
+            let link_span = span.link.as_ref().map(|_| span_index);
+            let text_style = span.style_name.as_ref()
+                .and_then(|name| gui.get_text_style(name))
+                .or_else(|| default_style.clone());
+            let text_style = match text_style {
+                None => continue,
+                Some(text_style) => text_style,
+            };
+            let font = match span.font_size {
+                None => text_style.font().as_ref().clone(),
+                Some(font_size) => text_style.font().with_size(font_size),
+            };
+            let color = match (span.color, link_span) {
+                (Some(color), _) => color,
+                (None, Some(_)) => self.link_color.get(),
+                (None, None) => *text_style.color(),
+            };
+            let spacing = text_style.spacing();
+
+            match &span.content {
+                SpanContent::Icon(image_name) => {
+                    if let Some(texture) = gui.get_image(image_name) {
+                        let height = font.size;
+                        let width = if texture.height > 0 { texture.width as f32 * height / texture.height as f32 } else { 0.0 };
+                        pieces.push(RichPiece { kind: PieceKind::Icon(texture), size: Size::new(width, height), baseline: font.baseline(), position: Vector2::zero(), line: 0, link_span });
+                    }
+                }
+                SpanContent::Text(text) => {
+                    for (line_index, line) in text.split('\n').enumerate() {
+                        if line_index > 0 {
+                            pieces.push(RichPiece { kind: PieceKind::LineBreak, size: Size::new(0.0, font.size), baseline: font.baseline(), position: Vector2::zero(), line: 0, link_span });
+                        }
+                        for (is_space, word) in split_words(line) {
+                            let size = font.measure_text(word, spacing);
+                            let kind = if is_space {
+                                PieceKind::Space
+                            } else {
+                                PieceKind::Word { text: word.to_string(), font: font.clone(), color, spacing }
+                            };
+                            pieces.push(RichPiece { kind, size, baseline: font.baseline(), position: Vector2::zero(), line: 0, link_span });
+                        }
+                    }
+                }
+            }
+        }
+        pieces
+    }
+
+    /// Place the pieces in lines no wider than `width`, with no limit with `None`
+    fn arrange_pieces(pieces: &[RichPiece], width: Option<f32>) -> PiecesArrangement {
+        let mut positions = vec![Vector2::zero(); pieces.len()];
+        let mut piece_lines = vec![0; pieces.len()];
+
+        // the pieces of each line, from the index of the first one to the index after the last one
+        let mut lines: Vec<(usize, usize)> = Vec::new();
+        let mut line_start = 0;
+        let mut x = 0.0;
+        for (index, piece) in pieces.iter().enumerate() {
+            let piece_width = piece.size.width();
+            let breaks = match piece.kind {
+                PieceKind::LineBreak => true,
+                PieceKind::Space => false,
+                _ => width.is_some_and(|width| x > 0.0 && x + piece_width > width),
+            };
+            if breaks {
+                lines.push((line_start, index));
+                line_start = index;
+                x = 0.0;
+            }
+            positions[index].x = x;
+            x += piece_width;
+        }
+        lines.push((line_start, pieces.len()));
+
+        let mut size = Size::empty();
+        let mut line_widths = Vec::new();
+        let mut y = 0.0;
+        for (line_index, (start, end)) in lines.into_iter().enumerate() {
+            let line = &pieces[start..end];
+            let ascent = line.iter().fold(0.0f32, |ascent, piece| ascent.max(piece.baseline));
+            let descent = line.iter().fold(0.0f32, |descent, piece| descent.max(piece.size.height() - piece.baseline));
+            let line_width = line.iter().zip(&positions[start..end])
+                .filter(|(piece, _)| !matches!(piece.kind, PieceKind::Space))
+                .fold(0.0f32, |line_width, (piece, position)| line_width.max(position.x + piece.size.width()));
+            for (index, piece) in line.iter().enumerate() {
+                positions[start + index].y = y + ascent - piece.baseline;
+                piece_lines[start + index] = line_index;
+            }
+            line_widths.push(line_width);
+            size.set_width(size.width().max(line_width));
+            y += ascent + descent;
+        }
+        size.set_height(y);
+
+        PiecesArrangement { positions, lines: piece_lines, line_widths, size }
+    }
+
+    fn text_width_limit(&self, widget_width: f32) -> Option<f32> {
+        if !self.wrap.get() {
+            return None;
+        }
+        Some((widget_width - self.padding().h_padding() - 2.0 * RICH_TEXT_BORDER).max(0.0))
+    }
+}
+
+/// Split a line in runs of spaces and runs of other characters, telling which ones are spaces
+fn split_words(line: &str) -> Vec<(bool, &str)> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut in_space = None;
+    for (index, c) in line.char_indices() {
+        let is_space = c == ' ';
+        if in_space.is_some_and(|in_space| in_space != is_space) {
+            words.push((!is_space, &line[start..index]));
+            start = index;
+        }
+        in_space = Some(is_space);
+    }
+    if let Some(in_space) = in_space {
+        words.push((in_space, &line[start..]));
+    }
+    words
+}
+
+#[derive(Clone, PartialEq)]
+enum MarkupTag {
+    Style(String),
+    Color(Color),
+    Size(f32),
+    Link(String),
+}
+
+/// Build the spans of a text with markup tags. Unknown tags are kept as text
+pub fn parse_markup(markup: &str) -> Vec<TextSpan> {
+    let mut spans = Vec::new();
+    let mut open_tags: Vec<(String, MarkupTag)> = Vec::new();
+    let mut text = String::new();
+
+    let flush = |text: &mut String, spans: &mut Vec<TextSpan>, open_tags: &[(String, MarkupTag)]| {
+        if !text.is_empty() {
+            spans.push(with_open_tags(TextSpan::text(text), open_tags));
+            text.clear();
+        }
+    };
+
+    let mut rest = markup;
+    while let Some(start) = rest.find('[') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("[[") {
+            text.push('[');
+            rest = after;
+            continue;
+        }
+        let end = match rest.find(']') {
+            None => break,
+            Some(end) => end,
+        };
+        let tag = &rest[1..end];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            if let Some(position) = open_tags.iter().rposition(|(open_name, _)| open_name == name) {
+                flush(&mut text, &mut spans, &open_tags);
+                open_tags.remove(position);
+                rest = &rest[end + 1..];
+                continue;
+            }
+        } else if let Some(image_name) = tag.strip_prefix("icon=") {
+            flush(&mut text, &mut spans, &open_tags);
+            spans.push(with_open_tags(TextSpan::icon(image_name), &open_tags));
+            rest = &rest[end + 1..];
+            continue;
+        } else if let Some((name, markup_tag)) = parse_tag(tag) {
+            flush(&mut text, &mut spans, &open_tags);
+            open_tags.push((name, markup_tag));
+            rest = &rest[end + 1..];
+            continue;
+        }
+
+        // not a tag, the bracket is a plain character
+        text.push('[');
+        rest = &rest[1..];
+    }
+    text.push_str(rest);
+    flush(&mut text, &mut spans, &open_tags);
+    spans
+}
+
+fn parse_tag(tag: &str) -> Option<(String, MarkupTag)> {
+    let (name, value) = match tag.split_once('=') {
+        None => (tag, None),
+        Some((name, value)) => (name, Some(value)),
+    };
+    let markup_tag = match (name, value) {
+        ("b", None) => MarkupTag::Style(RICH_TEXT_BOLD_STYLE.to_string()),
+        ("i", None) => MarkupTag::Style(RICH_TEXT_ITALIC_STYLE.to_string()),
+        ("style", Some(style_name)) => MarkupTag::Style(style_name.to_string()),
+        ("color", Some(color)) => MarkupTag::Color(parse_color(color)?),
+        ("size", Some(size)) => MarkupTag::Size(size.parse().ok()?),
+        ("link", Some(link_id)) => MarkupTag::Link(link_id.to_string()),
+        _ => return None,
+    };
+    Some((name.to_string(), markup_tag))
+}
+
+fn with_open_tags(mut span: TextSpan, open_tags: &[(String, MarkupTag)]) -> TextSpan {
+    for (_, markup_tag) in open_tags {
+        match markup_tag {
+            MarkupTag::Style(style_name) => span.style_name = Some(style_name.clone()),
+            MarkupTag::Color(color) => span.color = Some(*color),
+            MarkupTag::Size(size) => span.font_size = Some(*size),
+            MarkupTag::Link(link_id) => span.link = Some(link_id.clone()),
+        }
+    }
+    span
+}
+
+impl WidgetSpecific for RichTextPar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, gui: &Gui) -> Size {
+        let preferred_width = self.preferred_size().width();
+        let wrap_width = if preferred_width > 0.0 { self.text_width_limit(preferred_width) } else { None };
+        let pieces = self.build_pieces(gui);
+        let text_size = Self::arrange_pieces(&pieces, wrap_width).size;
+        self.pieces.replace(pieces);
+        // the new pieces are placed by the position step, even if the size is unchanged
+        self.invalidate_position(gui);
+
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&text_size.with_padding(&self.padding()).width_border(RICH_TEXT_BORDER));
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {
+        let mut pieces = self.pieces.borrow_mut();
+        let arrangement = Self::arrange_pieces(&pieces, self.text_width_limit(self.widget_width()));
+        for ((piece, position), line) in pieces.iter_mut().zip(arrangement.positions).zip(arrangement.lines) {
+            piece.position = position;
+            piece.line = line;
+        }
+        self.line_widths.replace(arrangement.line_widths);
+        self.text_size.set(arrangement.size);
+    }
+
+    fn height_for_width(&self, _gui: &Gui, width: f32) -> Option<f32> {
+        if !self.wrap.get() {
+            return None;
+        }
+        let text_size = Self::arrange_pieces(&self.pieces.borrow(), self.text_width_limit(width)).size;
+        let height = text_size.height() + self.padding().v_padding() + 2.0 * RICH_TEXT_BORDER;
+        Some(self.preferred_size().height().max(height))
+    }
+
+    fn baseline(&self, _gui: &Gui, height: f32) -> Option<f32> {
+        let pieces = self.pieces.borrow();
+        let first_piece = pieces.first()?;
+        // asked while the parent is laid out, so the pieces are placed for the current width
+        let arrangement = Self::arrange_pieces(&pieces, self.text_width_limit(self.widget_width()));
+        // the text is centered in the content
+        let padding = self.padding();
+        let content_height = height - padding.v_padding();
+        let origin_y = padding.top + (content_height - arrangement.size.height()) * 0.5;
+        Some(origin_y + arrangement.positions[0].y + first_piece.baseline)
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+
+        let mut hoovered_link = None;
+        if self.get_hoover_state() {
+            let mouse_position = mouse_state.mouse_position();
+            let local = Vector2::new(mouse_position.x - offset.x, mouse_position.y - offset.y);
+            hoovered_link = self.pieces.borrow().iter()
+                .filter(|piece| piece.link_span.is_some())
+                .find(|piece| self.piece_layout(piece).check_collision_point_rec(local))
+                .and_then(|piece| piece.link_span);
+        }
+        self.hoovered_link.set(hoovered_link);
+
+        // like the click of the widget, the link is armed by the press and clicked by the release.
+        // A press on a link does not arm the widget, so it does not send its click
+        let mut armed_link = self.armed_link.get();
+        if mouse_state.left().is_pressed() && hoovered_link.is_some() {
+            armed_link = hoovered_link;
+            self.state.armed.set(false);
+        }
+        if let Some(span_index) = hoovered_link.filter(|_| mouse_state.left().is_released() && armed_link == hoovered_link) {
+            if let Some(link_id) = self.spans.borrow().get(span_index).and_then(|span| span.link.as_ref()) {
+                gui.add_event(LinkClicked(LinkPar::new(link_id)))
+            }
+        }
+        self.armed_link.set(armed_link.filter(|_| mouse_state.left().is_down()));
+    }
+
+    fn render_my_visual(&self, _gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let hoovered_link = self.hoovered_link.get();
+
+        for piece in self.pieces.borrow().iter() {
+            let mut layout = self.piece_layout(piece);
+            layout.x += offset.x;
+            layout.y += offset.y;
+            let position = Vector2::new(layout.x, layout.y);
+            let hoovered = hoovered_link.is_some() && piece.link_span == hoovered_link;
+
+            if hoovered && !matches!(piece.kind, PieceKind::LineBreak) {
+                let y = layout.y + piece.baseline + 1.0;
+                d.draw_line_v(Vector2::new(layout.x, y), Vector2::new(layout.x + layout.width, y), self.link_hoover_color.get());
+            }
+
+            match &piece.kind {
+                PieceKind::Word { text, font, color, spacing } => {
+                    let color = if hoovered { self.link_hoover_color.get() } else { *color };
+                    font.draw_text(d, text, &position, *spacing, color)
+                }
+                PieceKind::Icon(texture) => {
+                    let source = Rectangle::new(0.0, 0.0, texture.width as f32, texture.height as f32);
+                    let destination = Rectangle::new(position.x, position.y, piece.size.width(), piece.size.height());
+                    d.draw_texture_pro(texture.as_ref(), source, destination, Vector2::zero(), 0.0, Color::WHITE);
+                }
+                PieceKind::Space | PieceKind::LineBreak => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use raylib::prelude::Color;
+
+    use super::{parse_markup, TextSpan, RICH_TEXT_BOLD_STYLE};
+
+    #[test]
+    fn keeps_a_text_without_tags_in_one_span() {
+        assert_eq!(parse_markup("plain text"), vec![TextSpan::text("plain text")]);
+    }
+
+    #[test]
+    fn applies_the_open_tags_to_the_spans() {
+        assert_eq!(parse_markup("a [b]bold [color=red]red[/color][/b] b"), vec![
+            TextSpan::text("a "),
+            TextSpan::text("bold ").with_style(RICH_TEXT_BOLD_STYLE),
+            TextSpan::text("red").with_style(RICH_TEXT_BOLD_STYLE).with_color(Color::RED),
+            TextSpan::text(" b"),
+        ]);
+    }
+
+    #[test]
+    fn parses_icons_sizes_and_links() {
+        assert_eq!(parse_markup("[size=24][icon=save][link=open]open[/link][/size]"), vec![
+            TextSpan::icon("save").with_font_size(24.0),
+            TextSpan::text("open").with_font_size(24.0).with_link("open"),
+        ]);
+    }
+
+    #[test]
+    fn reads_a_double_bracket_as_a_bracket() {
+        assert_eq!(parse_markup("[[b] a[[1]"), vec![TextSpan::text("[b] a[1]")]);
+    }
+
+    #[test]
+    fn keeps_unknown_and_unterminated_tags_as_text() {
+        assert_eq!(parse_markup("[x]a[/b] [color=nope]b"), vec![TextSpan::text("[x]a[/b] [color=nope]b")]);
+        assert_eq!(parse_markup("a [b"), vec![TextSpan::text("a [b")]);
+    }
+
+    #[test]
+    fn closes_the_tags_left_open_at_the_end() {
+        assert_eq!(parse_markup("[b]bold"), vec![TextSpan::text("bold").with_style(RICH_TEXT_BOLD_STYLE)]);
+    }
+}
@@ -0,0 +1,203 @@
+use std::cell::Cell;
+
+use raylib::prelude::*;
+
+use crate::mouse::MouseState;
+use crate::orientation::Orientation;
+
+pub const SCROLL_BAR_THICKNESS: f32 = 10.0;
+const SCROLL_BAR_MIN_THUMB_LENGTH: f32 = 20.0;
+const SCROLL_BAR_TRACK_COLOR: Color = Color::LIGHTGRAY;
+const SCROLL_BAR_THUMB_COLOR: Color = Color::GRAY;
+
+/// A scroll bar used by the widgets that display content larger than their viewport.
+/// It is not a widget by itself : the owning widget sets its layout, forwards the mouse
+/// actions to it and renders it.
+pub struct ScrollBar {
+    orientation: Orientation,
+    /// the scroll value, in pixels, between 0 and `max_value`
+    value: Cell<f32>,
+    /// the length of the scrolled content along the orientation
+    content_length: Cell<f32>,
+    /// the length of the visible part of the content along the orientation
+    viewport_length: Cell<f32>,
+    /// the layout of the bar, in the same coordinates as the content layout of the owning widget
+    layout: Cell<Rectangle>,
+    drag_in_progress: Cell<bool>,
+    drag_starting_value: Cell<f32>,
+}
+
+impl ScrollBar {
+    pub fn new(orientation: Orientation) -> Self {
+        Self {
+            orientation,
+            value: Cell::new(0.0),
+            content_length: Cell::new(0.0),
+            viewport_length: Cell::new(0.0),
+            layout: Cell::new(Rectangle::default()),
+            drag_in_progress: Cell::new(false),
+            drag_starting_value: Cell::new(0.0),
+        }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value.get()
+    }
+
+    pub fn max_value(&self) -> f32 {
+        (self.content_length.get() - self.viewport_length.get()).max(0.0)
+    }
+
+    /// true if the content does not fit in the viewport
+    pub fn is_needed(&self) -> bool {
+        self.max_value() > 0.0
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.drag_in_progress.get()
+    }
+
+    /// Set the scroll value (clamped to the valid range). Returns true if the value changed
+    pub fn set_value(&self, value: f32) -> bool {
+        let value = value.clamp(0.0, self.max_value());
+        if value == self.value.get() {
+            return false;
+        }
+        self.value.set(value);
+        true
+    }
+
+    pub fn scroll_by(&self, delta: f32) -> bool {
+        self.set_value(self.value.get() + delta)
+    }
+
+    /// Scroll the minimal amount so that the range [start,end] of the content is visible.
+    /// Returns true if the value changed
+    pub fn scroll_to_show(&self, start: f32, end: f32) -> bool {
+        let value = self.value.get();
+        let viewport_length = self.viewport_length.get();
+        if start < value {
+            self.set_value(start)
+        } else if end > value + viewport_length {
+            self.set_value(end.min(start + viewport_length) - viewport_length)
+        } else {
+            false
+        }
+    }
+
+    /// Update the lengths of the content and of the viewport. The value is clamped
+    /// if needed. Returns true if the value changed
+    pub fn set_lengths(&self, content_length: f32, viewport_length: f32) -> bool {
+        self.content_length.set(content_length);
+        self.viewport_length.set(viewport_length);
+        self.set_value(self.value.get())
+    }
+
+    pub fn layout(&self) -> Rectangle {
+        self.layout.get()
+    }
+
+    pub fn set_layout(&self, layout: Rectangle) {
+        self.layout.set(layout)
+    }
+
+    fn along(&self, vector: &Vector2) -> f32 {
+        if self.orientation.is_horizontal() { vector.x } else { vector.y }
+    }
+
+    fn track_length(&self) -> f32 {
+        let layout = self.layout.get();
+        if self.orientation.is_horizontal() { layout.width } else { layout.height }
+    }
+
+    fn thumb_length(&self) -> f32 {
+        let track_length = self.track_length();
+        let content_length = self.content_length.get();
+        if content_length <= 0.0 {
+            return track_length;
+        }
+        let length = track_length * self.viewport_length.get() / content_length;
+        length.max(SCROLL_BAR_MIN_THUMB_LENGTH.min(track_length)).min(track_length)
+    }
+
+    fn thumb_layout(&self) -> Rectangle {
+        let mut layout = self.layout.get();
+        let thumb_length = self.thumb_length();
+        let max_value = self.max_value();
+        let position = if max_value > 0.0 { (self.track_length() - thumb_length) * self.value.get() / max_value } else { 0.0 };
+
+        match self.orientation {
+            Orientation::Horizontal => {
+                layout.x += position;
+                layout.width = thumb_length;
+            }
+            Orientation::Vertical => {
+                layout.y += position;
+                layout.height = thumb_length;
+            }
+        }
+        layout
+    }
+
+    /// Handle the dragging of the thumb and the clicks on the track.
+    /// Returns true if the value changed
+    pub fn update_action(&self, offset: &Vector2, mouse_state: &MouseState) -> bool {
+        if !self.is_needed() {
+            self.drag_in_progress.set(false);
+            return false;
+        }
+
+        let drag_info = mouse_state.drag_info();
+
+        if drag_info.started() {
+            let mouse_position = *drag_info.starting_position();
+            let mut thumb_layout = self.thumb_layout();
+            thumb_layout.x += offset.x;
+            thumb_layout.y += offset.y;
+
+            if thumb_layout.check_collision_point_rec(mouse_position) {
+                self.drag_in_progress.set(true);
+                self.drag_starting_value.set(self.value.get());
+                return false;
+            }
+
+            let mut track_layout = self.layout.get();
+            track_layout.x += offset.x;
+            track_layout.y += offset.y;
+            if track_layout.check_collision_point_rec(mouse_position) {
+                let page = self.viewport_length.get();
+                let before_thumb = self.along(&mouse_position) < self.along(&Vector2::new(thumb_layout.x, thumb_layout.y));
+                return self.scroll_by(if before_thumb { -page } else { page });
+            }
+        } else if drag_info.in_progress() && self.drag_in_progress.get() {
+            let free_length = self.track_length() - self.thumb_length();
+            if free_length <= 0.0 {
+                return false;
+            }
+            let displacement = self.along(drag_info.delta());
+            return self.set_value(self.drag_starting_value.get() + displacement * self.max_value() / free_length);
+        } else if drag_info.done() {
+            self.drag_in_progress.set(false);
+        }
+        false
+    }
+
+    pub fn render(&self, d: &mut impl RaylibDraw, offset: &Vector2) {
+        if !self.is_needed() {
+            return;
+        }
+        let mut track_layout = self.layout.get();
+        track_layout.x += offset.x;
+        track_layout.y += offset.y;
+        d.draw_rectangle_rec(track_layout, SCROLL_BAR_TRACK_COLOR);
+
+        let mut thumb_layout = self.thumb_layout();
+        thumb_layout.x += offset.x;
+        thumb_layout.y += offset.y;
+        d.draw_rectangle_rec(thumb_layout, SCROLL_BAR_THUMB_COLOR);
+    }
+}
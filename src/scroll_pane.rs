@@ -0,0 +1,307 @@
+use std::cell::Cell;
+use std::ops::Deref;
+
+use generational_arena::Index;
+use raylib::prelude::*;
+
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::orientation::Orientation;
+use crate::scroll_bar::{ScrollBar, SCROLL_BAR_THICKNESS};
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::{DirtyFlags, LayoutableWidget, WidgetSpecific};
+
+const SCROLL_PANE_WHEEL_STEP: f32 = 40.0;
+
+/// When a scroll bar of a scroll pane is displayed
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ScrollBarPolicy {
+    /// only if the content does not fit in the viewport
+    Auto,
+    Always,
+    /// the content is never scrolled along this axis and is clamped to the viewport like in the other containers
+    Never,
+}
+
+/// A container that lays its children out at their full computed size and displays
+/// them through a viewport. The content is scrolled with the scroll bars, the mouse
+/// wheel (vertical first, horizontal if there is no vertical scroll bar) or by
+/// dragging it with the middle button.
+pub struct ScrollPanePar {
+    widget_data: WidgetData,
+    h_scroll_bar: ScrollBar,
+    v_scroll_bar: ScrollBar,
+    h_policy: Cell<ScrollBarPolicy>,
+    v_policy: Cell<ScrollBarPolicy>,
+    viewport_size: Cell<Size>,
+    content_size: Cell<Size>,
+    pan_in_progress: Cell<bool>,
+    pan_starting_position: Cell<Vector2>,
+    pan_starting_value: Cell<Vector2>,
+}
+
+impl Deref for ScrollPanePar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl ScrollPanePar {
+    pub fn new() -> Self {
+        Self {
+            widget_data: WidgetData::new(),
+            h_scroll_bar: ScrollBar::new(Orientation::Horizontal),
+            v_scroll_bar: ScrollBar::new(Orientation::Vertical),
+            h_policy: Cell::new(ScrollBarPolicy::Auto),
+            v_policy: Cell::new(ScrollBarPolicy::Auto),
+            viewport_size: Cell::new(Size::empty()),
+            content_size: Cell::new(Size::empty()),
+            pan_in_progress: Cell::new(false),
+            pan_starting_position: Cell::new(Vector2::default()),
+            pan_starting_value: Cell::new(Vector2::default()),
+        }
+    }
+
+    pub fn h_policy(&self) -> ScrollBarPolicy {
+        self.h_policy.get()
+    }
+
+    pub fn v_policy(&self) -> ScrollBarPolicy {
+        self.v_policy.get()
+    }
+
+    pub fn set_h_policy(&self, gui: &Gui, policy: ScrollBarPolicy) -> &ScrollPanePar {
+        self.h_policy.set(policy);
+        self.invalidate_preferred_size(gui);
+        self.invalidate_content_size(gui);
+        self
+    }
+
+    pub fn set_v_policy(&self, gui: &Gui, policy: ScrollBarPolicy) -> &ScrollPanePar {
+        self.v_policy.set(policy);
+        self.invalidate_preferred_size(gui);
+        self.invalidate_content_size(gui);
+        self
+    }
+
+    /// The scrolled distance of the content, in pixels
+    pub fn scroll_position(&self) -> Vector2 {
+        Vector2::new(self.h_scroll_bar.value(), self.v_scroll_bar.value())
+    }
+
+    /// Scroll the content (the values are clamped to the scrollable range)
+    pub fn set_scroll_position(&self, gui: &Gui, position: Vector2) -> &ScrollPanePar {
+        let mut scrolled = self.h_scroll_bar.set_value(position.x);
+        scrolled |= self.v_scroll_bar.set_value(position.y);
+        if scrolled {
+            self.invalidate_position(gui);
+        }
+        self
+    }
+
+    /// Scroll the minimal amount so that the provided widget, a descendant of the pane,
+    /// is visible. The layout of the last frame is used
+    pub fn scroll_to_widget(&self, gui: &Gui, widget: Index) -> &ScrollPanePar {
+        let tree_index = match self.get_tree_index() {
+            None => return self,
+            Some(tree_index) => tree_index,
+        };
+        let mut layout = match gui.get_widget(widget) {
+            None => return self,
+            Some(w) => w.widget_layout(),
+        };
+
+        // express the layout of the widget relatively to the content origin of the pane
+        let mut parent = gui.get_parent(widget);
+        loop {
+            match parent {
+                None => return self,
+                Some(parent_index) if parent_index == tree_index => break,
+                Some(parent_index) => {
+                    if let Some(w) = gui.get_widget(parent_index) {
+                        let content_layout = w.content_layout();
+                        layout.x += content_layout.x;
+                        layout.y += content_layout.y;
+                    }
+                    parent = gui.get_parent(parent_index);
+                }
+            }
+        }
+
+        let h_value = self.h_scroll_bar.value();
+        let v_value = self.v_scroll_bar.value();
+        let mut scrolled = self.h_scroll_bar.scroll_to_show(layout.x + h_value, layout.x + layout.width + h_value);
+        scrolled |= self.v_scroll_bar.scroll_to_show(layout.y + v_value, layout.y + layout.height + v_value);
+        if scrolled {
+            self.invalidate_position(gui);
+        }
+        self
+    }
+
+    /// Decide which scroll bars are displayed for the content and the available size.
+    /// A displayed scroll bar reduces the viewport, which can require the other one
+    fn scroll_bars_needed(&self, content_size: &Size, available_size: &Size) -> (bool, bool) {
+        let needed = |policy: ScrollBarPolicy, content: f32, available: f32| match policy {
+            ScrollBarPolicy::Always => true,
+            ScrollBarPolicy::Never => false,
+            ScrollBarPolicy::Auto => content > available,
+        };
+
+        let mut h_needed = needed(self.h_policy.get(), content_size.width(), available_size.width());
+        let v_needed = needed(self.v_policy.get(), content_size.height(),
+                              available_size.height() - if h_needed { SCROLL_BAR_THICKNESS } else { 0.0 });
+        if v_needed && !h_needed {
+            h_needed = needed(self.h_policy.get(), content_size.width(), available_size.width() - SCROLL_BAR_THICKNESS);
+        }
+        (h_needed, v_needed)
+    }
+}
+
+impl WidgetSpecific for ScrollPanePar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, gui: &Gui) -> Size {
+        let tree_index = match self.get_tree_index() {
+            None => return Size::empty(),
+            Some(tree_index) => tree_index,
+        };
+
+        let mut children_size = Size::empty();
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(w) = gui.get_widget(child_index) {
+                children_size.max_mut(&w.compute_computed_size(gui));
+            }
+        }
+
+        if self.h_policy.get() == ScrollBarPolicy::Always {
+            children_size.set_height(children_size.height() + SCROLL_BAR_THICKNESS);
+        }
+        if self.v_policy.get() == ScrollBarPolicy::Always {
+            children_size.set_width(children_size.width() + SCROLL_BAR_THICKNESS);
+        }
+
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&children_size.with_padding(&self.padding()));
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, gui: &Gui, available_size: Size) {
+        let tree_index = match self.get_tree_index() {
+            None => return,
+            Some(tree_index) => tree_index,
+        };
+        let available_size = available_size.without_padding(&self.padding());
+
+        let mut children_size = Size::empty();
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(w) = gui.get_widget(child_index) {
+                children_size.max_mut(&w.compute_computed_size(gui));
+            }
+        }
+
+        let (h_needed, v_needed) = self.scroll_bars_needed(&children_size, &available_size);
+        let viewport_size = Size::new(
+            (available_size.width() - if v_needed { SCROLL_BAR_THICKNESS } else { 0.0 }).max(0.0),
+            (available_size.height() - if h_needed { SCROLL_BAR_THICKNESS } else { 0.0 }).max(0.0),
+        );
+        self.viewport_size.set(viewport_size);
+
+        // the children get all the space they want along the scrolled axes
+        let available_size_for_children = Size::new(
+            if self.h_policy.get() == ScrollBarPolicy::Never { viewport_size.width() } else { viewport_size.width().max(children_size.width()) },
+            if self.v_policy.get() == ScrollBarPolicy::Never { viewport_size.height() } else { viewport_size.height().max(children_size.height()) },
+        );
+
+        let mut content_size = Size::empty();
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(w) = gui.get_widget(child_index) {
+                w.update_content_size(gui, &available_size_for_children);
+                let widget_layout = w.widget_layout();
+                content_size.max_mut(&Size::new(widget_layout.width, widget_layout.height));
+            }
+        }
+        self.content_size.set(content_size);
+
+        let h_length = if h_needed { content_size.width() } else { 0.0 };
+        let v_length = if v_needed { content_size.height() } else { 0.0 };
+        self.h_scroll_bar.set_lengths(h_length, viewport_size.width());
+        self.v_scroll_bar.set_lengths(v_length, viewport_size.height());
+    }
+
+    fn compute_child_positions(&self, gui: &Gui) {
+        let content_layout = self.content_layout();
+        let viewport_size = self.viewport_size.get();
+        let viewport = Rectangle::new(content_layout.x, content_layout.y, viewport_size.width(), viewport_size.height());
+        self.set_children_clip(Some(viewport));
+
+        self.h_scroll_bar.set_layout(Rectangle::new(viewport.x, viewport.y + viewport.height, viewport.width, SCROLL_BAR_THICKNESS));
+        self.v_scroll_bar.set_layout(Rectangle::new(viewport.x + viewport.width, viewport.y, SCROLL_BAR_THICKNESS, viewport.height));
+
+        let tree_index = match self.get_tree_index() {
+            None => return,
+            Some(tree_index) => tree_index,
+        };
+
+        let content_size = self.content_size.get();
+        let scroll_position = self.scroll_position();
+
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(w) = gui.get_widget(child_index) {
+                // the children are positioned in the whole content, then the content is shifted by the scroll position
+                w.compute_default_target(&viewport_size.max(&content_size));
+                let widget_layout = w.widget_layout();
+                w.set_widget_target(&Vector2::new(widget_layout.x - scroll_position.x, widget_layout.y - scroll_position.y));
+                // the layouts the child keeps in the coordinates of the pane move with the scroll
+                w.set_dirty_flag(DirtyFlags::POSITION);
+                w.update_child_positions(gui)
+            }
+        }
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        let mut scrolled = self.h_scroll_bar.update_action(offset, mouse_state);
+        scrolled |= self.v_scroll_bar.update_action(offset, mouse_state);
+
+        let hoovered = self.get_hoover_state();
+        let wheel_move = mouse_state.wheel_move();
+        if hoovered && wheel_move != 0.0 {
+            let scroll_bar = if self.v_scroll_bar.is_needed() { &self.v_scroll_bar } else { &self.h_scroll_bar };
+            scrolled |= scroll_bar.scroll_by(-wheel_move * SCROLL_PANE_WHEEL_STEP);
+        }
+
+        let middle = mouse_state.middle();
+        let mouse_position = *mouse_state.mouse_position();
+        if middle.is_pressed() && hoovered {
+            self.pan_in_progress.set(true);
+            self.pan_starting_position.set(mouse_position);
+            self.pan_starting_value.set(self.scroll_position());
+        } else if middle.is_down() && self.pan_in_progress.get() {
+            let starting_position = self.pan_starting_position.get();
+            let starting_value = self.pan_starting_value.get();
+            scrolled |= self.h_scroll_bar.set_value(starting_value.x - (mouse_position.x - starting_position.x));
+            scrolled |= self.v_scroll_bar.set_value(starting_value.y - (mouse_position.y - starting_position.y));
+        } else {
+            self.pan_in_progress.set(false);
+        }
+
+        if scrolled {
+            self.invalidate_position(gui);
+        }
+
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, _gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        self.h_scroll_bar.render(d, offset);
+        self.v_scroll_bar.render(d, offset);
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::BTreeSet;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SelectionMode {
+    None,
+    Single,
+    Multiple,
+}
+
+/// The set of selected items of a widget displaying a collection (list, table...)
+pub struct Selection {
+    mode: SelectionMode,
+    selected: BTreeSet<usize>,
+}
+
+impl Selection {
+    pub fn new(mode: SelectionMode) -> Self {
+        Self { mode, selected: BTreeSet::new() }
+    }
+
+    pub fn mode(&self) -> SelectionMode {
+        self.mode
+    }
+
+    /// Change the selection mode. Returns true if the selection
+    /// changed to fit the new mode
+    pub fn set_mode(&mut self, mode: SelectionMode) -> bool {
+        self.mode = mode;
+        match mode {
+            SelectionMode::None => self.clear(),
+            SelectionMode::Single if self.selected.len() > 1 => {
+                let first = *self.selected.iter().next().unwrap();
+                self.selected.retain(|index| *index == first);
+                true
+            }
+            _ => false
+        }
+    }
+
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// The selected indices in increasing order
+    pub fn selected(&self) -> Vec<usize> {
+        self.selected.iter().cloned().collect()
+    }
+
+    /// Apply a click on the item at `index`: the item replaces the selection
+    /// in single mode and is toggled in multiple mode.
+    /// Returns true if the selection changed
+    pub fn click(&mut self, index: usize) -> bool {
+        match self.mode {
+            SelectionMode::None => false,
+            SelectionMode::Single => self.select(index),
+            SelectionMode::Multiple => {
+                if !self.selected.remove(&index) {
+                    self.selected.insert(index);
+                }
+                true
+            }
+        }
+    }
+
+    /// Select the item at `index`. Returns true if the selection changed
+    pub fn select(&mut self, index: usize) -> bool {
+        match self.mode {
+            SelectionMode::None => false,
+            SelectionMode::Single => {
+                if self.selected.len() == 1 && self.selected.contains(&index) {
+                    return false;
+                }
+                self.selected.clear();
+                self.selected.insert(index)
+            }
+            SelectionMode::Multiple => self.selected.insert(index)
+        }
+    }
+
+    /// Remove all the items from the selection. Returns true if the selection changed
+    pub fn clear(&mut self) -> bool {
+        let changed = !self.selected.is_empty();
+        self.selected.clear();
+        changed
+    }
+
+    /// Remove the items with an index greater or equal to `count`.
+    /// Returns true if the selection changed
+    pub fn truncate(&mut self, count: usize) -> bool {
+        let nb_selected = self.selected.len();
+        self.selected.retain(|index| *index < count);
+        nb_selected != self.selected.len()
+    }
+}
@@ -8,6 +8,36 @@ use std::ops::Deref;
 use crate::mouse::MouseState;
 use crate::event::Event::Drag;
 use crate::event::DragPar;
+use crate::keyboard::KeyboardState;
+use crate::orientation::Orientation;
+
+/// How the values are spread along the bar
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SliderScale {
+    Linear,
+    /// equal ratios take equal lengths, only used when both bounds are strictly positive
+    Logarithmic,
+}
+
+/// What a press on the bar outside of the cursor does
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TrackClick {
+    /// the cursor jumps under the mouse and follows it until the button is released
+    Jump,
+    /// the value moves by a page toward the mouse
+    Page,
+}
+
+/// Build the text displayed for a value
+pub type ValueFormatter = Box<dyn Fn(f32) -> String>;
+
+/// A graduation along the slider bar
+struct SliderTick {
+    value: f32,
+    major: bool,
+    label: String,
+    label_size: Size,
+}
 
 pub struct SliderPar {
     widget_data: WidgetData,
@@ -19,7 +49,22 @@ pub struct SliderPar {
     drag_in_progress: Cell<bool>,
     drag_value: Cell<f32>,
     drag_starting_position: Cell<Vector2>,
-    cursor_layout:Cell<Rectangle>
+    cursor_layout:Cell<Rectangle>,
+    orientation: Cell<Orientation>,
+    inverted: Cell<bool>,
+    major_tick_step: Cell<Option<f32>>,
+    minor_tick_count: Cell<u32>,
+    tick_labels: Cell<bool>,
+    ticks: RefCell<Vec<SliderTick>>,
+    /// the room taken by the ticks and their labels beside the bar
+    tick_band: Cell<f32>,
+    /// the bar without the tick band, in the same coordinates as the content layout
+    bar_layout: Cell<Rectangle>,
+    step: Cell<Option<f32>>,
+    page_step: Cell<Option<f32>>,
+    scale: Cell<SliderScale>,
+    track_click: Cell<TrackClick>,
+    formatter: RefCell<Option<ValueFormatter>>,
 }
 
 impl Deref for SliderPar {
@@ -30,7 +75,9 @@ impl Deref for SliderPar {
     }
 }
 
+/// the size of the bar across its orientation
 const SLIDER_BAR_HEIGHT: f32 = 20.0;
+/// the size of the bar along its orientation
 const SLIDER_BAR_WIDTH: f32 = 100.0;
 const SLIDER_BAR_THICKNESS: f32 = 1.0;
 const SLIDER_BAR_COLOR: Color = Color::GRAY;
@@ -38,13 +85,24 @@ const SLIDER_BAR_COLOR: Color = Color::GRAY;
 
 const SLIDER_CURSOR_SPACING: f32 = 2.0;
 const SLIDER_CURSOR_HEIGHT: f32 = 30.0;
+/// the size of the cursor along the orientation of the bar
 const SLIDER_CURSOR_WIDTH: f32 = 5.0;
 const SLIDER_CURSOR_THICKNESS: f32 = 1.0;
 const SLIDER_CURSOR_COLOR: Color = Color::BLACK;
 
+const SLIDER_MAJOR_TICK_LENGTH: f32 = 8.0;
+const SLIDER_MINOR_TICK_LENGTH: f32 = 4.0;
+const SLIDER_TICK_LABEL_SPACING: f32 = 2.0;
+const SLIDER_TICK_COLOR: Color = Color::DARKGRAY;
+
+/// the part of the bar moved by the arrow keys and the wheel when there is no step
+const SLIDER_DEFAULT_STEP_RATIO: f32 = 0.01;
+/// the part of the bar moved by a page when there is no page step
+const SLIDER_DEFAULT_PAGE_RATIO: f32 = 0.1;
+
 impl SliderPar {
     pub fn new() -> Self {
-        Self {
+        let par = Self {
             widget_data: WidgetData::new(),
             value: Cell::new(50.0),
             value_min: Cell::new(0.0),
@@ -55,7 +113,23 @@ impl SliderPar {
             drag_value: Cell::new(50.0),
             drag_starting_position: Cell::new(Vector2::default()),
             cursor_layout:Cell::new(Rectangle::default()),
-        }
+            orientation: Cell::new(Orientation::Horizontal),
+            inverted: Cell::new(false),
+            major_tick_step: Cell::new(None),
+            minor_tick_count: Cell::new(0),
+            tick_labels: Cell::new(false),
+            ticks: RefCell::new(Vec::new()),
+            tick_band: Cell::new(0.0),
+            bar_layout: Cell::new(Rectangle::default()),
+            step: Cell::new(None),
+            page_step: Cell::new(None),
+            scale: Cell::new(SliderScale::Linear),
+            track_click: Cell::new(TrackClick::Jump),
+            formatter: RefCell::new(None),
+        };
+        par.set_hooverable(true);
+        par.set_focusable(true);
+        par
     }
 
     pub fn get_value(&self) -> f32 {
@@ -70,8 +144,9 @@ impl SliderPar {
         self.value_max.get()
     }
 
+    /// The value is rounded to the closest step, if there is one
     pub fn set_value(&self, gui: &Gui, value: f32) -> &SliderPar {
-        self.value.set(value);
+        self.value.set(self.snap_to_step(value));
         self.invalidate_preferred_size(gui);
         self
     }
@@ -89,8 +164,99 @@ impl SliderPar {
     }
 
 
+    pub fn orientation(&self) -> Orientation {
+        self.orientation.get()
+    }
+
+    pub fn set_orientation(&self, gui: &Gui, orientation: Orientation) -> &SliderPar {
+        self.orientation.set(orientation);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn is_inverted(&self) -> bool {
+        self.inverted.get()
+    }
+
+    /// By default the minimum is on the left of a horizontal slider and at the bottom of a
+    /// vertical one. An inverted slider puts it on the right or at the top
+    pub fn set_inverted(&self, gui: &Gui, inverted: bool) -> &SliderPar {
+        self.inverted.set(inverted);
+        self.invalidate_position(gui);
+        self
+    }
+
+    /// Draw a major tick every `step` from the minimum value, or no tick at all with `None`
+    pub fn set_major_ticks(&self, gui: &Gui, step: Option<f32>) -> &SliderPar {
+        self.major_tick_step.set(step.filter(|step| *step > 0.0));
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// The number of minor ticks drawn between two major ticks
+    pub fn set_minor_ticks(&self, gui: &Gui, count: u32) -> &SliderPar {
+        self.minor_tick_count.set(count);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// Show the value of each major tick beside it
+    pub fn set_tick_labels(&self, gui: &Gui, tick_labels: bool) -> &SliderPar {
+        self.tick_labels.set(tick_labels);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// Snap the values to multiples of `step` from the minimum value, or leave them continuous with `None`.
+    /// The step is also the increment of the arrow keys and of the mouse wheel. The current value is snapped too
+    pub fn set_step(&self, gui: &Gui, step: Option<f32>) -> &SliderPar {
+        self.step.set(step.filter(|step| *step > 0.0));
+        self.value.set(self.snap_to_step(self.value.get()));
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// The increment of the page keys and of the track clicks in page mode,
+    /// a tenth of the bar with `None`
+    pub fn set_page_step(&self, gui: &Gui, page_step: Option<f32>) -> &SliderPar {
+        self.page_step.set(page_step.filter(|step| *step > 0.0));
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn scale(&self) -> SliderScale {
+        self.scale.get()
+    }
+
+    pub fn set_scale(&self, gui: &Gui, scale: SliderScale) -> &SliderPar {
+        self.scale.set(scale);
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn set_track_click(&self, track_click: TrackClick) -> &SliderPar {
+        self.track_click.set(track_click);
+        self
+    }
+
+    /// Replace the default `{:5.1}` format of the displayed value and of the tick labels
+    pub fn set_formatter(&self, gui: &Gui, formatter: impl Fn(f32) -> String + 'static) -> &SliderPar {
+        self.formatter.replace(Some(Box::new(formatter)));
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn clear_formatter(&self, gui: &Gui) -> &SliderPar {
+        self.formatter.replace(None);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
     fn format_value(&self, value:f32) -> String {
-        format!("{:5.1}", value)
+        match self.formatter.borrow().as_ref() {
+            None => format!("{:5.1}", value),
+            Some(formatter) => formatter(value),
+        }
     }
 
     fn measure_value(&self, formatted_value: &str) -> Size {
@@ -104,6 +270,224 @@ impl SliderPar {
         if self.drag_in_progress.get() {self.drag_value.get()} else {self.value.get()}
     }
 
+    /// The position of the value along the bar, from 0 at the start of the bar to 1 at its end,
+    /// the start being the left of a horizontal bar and the top of a vertical one
+    fn value_to_fraction(&self, value: f32) -> f32 {
+        let value_min = self.value_min.get();
+        let value_max = self.value_max.get();
+        let fraction = if value_max == value_min {
+            0.0
+        } else if self.is_logarithmic() {
+            ((value.max(f32::MIN_POSITIVE).ln() - value_min.ln()) / (value_max.ln() - value_min.ln())).clamp(0.0, 1.0)
+        } else {
+            ((value - value_min) / (value_max - value_min)).clamp(0.0, 1.0)
+        };
+        // the minimum of a vertical slider is at the bottom, unless inverted
+        if self.inverted.get() != self.orientation.get().is_vertical() { 1.0 - fraction } else { fraction }
+    }
+
+    fn fraction_to_value(&self, fraction: f32) -> f32 {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let fraction = if self.inverted.get() != self.orientation.get().is_vertical() { 1.0 - fraction } else { fraction };
+        let value_min = self.value_min.get();
+        let value_max = self.value_max.get();
+        let value = if self.is_logarithmic() {
+            (fraction * (value_max.ln() - value_min.ln()) + value_min.ln()).exp()
+        } else {
+            fraction * (value_max - value_min) + value_min
+        };
+        self.snap(value)
+    }
+
+    fn is_logarithmic(&self) -> bool {
+        self.scale.get() == SliderScale::Logarithmic && self.value_min.get() > 0.0 && self.value_max.get() > 0.0
+    }
+
+    /// Round the value to the closest step, if there is one
+    fn snap_to_step(&self, value: f32) -> f32 {
+        let value_min = self.value_min.get();
+        match self.step.get() {
+            None => value,
+            Some(step) => value_min + ((value - value_min) / step).round() * step,
+        }
+    }
+
+    /// Round the value to the closest step and keep it in the range
+    fn snap(&self, value: f32) -> f32 {
+        let value_min = self.value_min.get();
+        let value_max = self.value_max.get();
+        self.snap_to_step(value).clamp(value_min.min(value_max), value_min.max(value_max))
+    }
+
+    /// The value moved by a step of the arrow keys and the wheel, toward the maximum with a positive direction
+    fn small_move(&self, value: f32, direction: f32) -> f32 {
+        match self.step.get() {
+            Some(step) => value + direction * step,
+            None => self.move_by_ratio(value, direction * SLIDER_DEFAULT_STEP_RATIO),
+        }
+    }
+
+    /// The value moved by a page, toward the maximum with a positive direction
+    fn page_move(&self, value: f32, direction: f32) -> f32 {
+        match self.page_step.get() {
+            Some(page_step) => value + direction * page_step,
+            None => self.move_by_ratio(value, direction * SLIDER_DEFAULT_PAGE_RATIO),
+        }
+    }
+
+    /// The value moved by a part of the length of the bar, so by a factor on a logarithmic scale
+    fn move_by_ratio(&self, value: f32, ratio: f32) -> f32 {
+        let value_min = self.value_min.get();
+        let value_max = self.value_max.get();
+        if self.is_logarithmic() {
+            (value.max(f32::MIN_POSITIVE).ln() + ratio * (value_max.ln() - value_min.ln()).abs()).exp()
+        } else {
+            value + ratio * (value_max - value_min).abs()
+        }
+    }
+
+    /// Commit a value changed in one go, by the keyboard, the wheel or a page click
+    fn change_value(&self, gui: &Gui, value: f32) {
+        let value = self.snap(value);
+        if value == self.value.get() {
+            return;
+        }
+        self.value.set(value);
+        self.invalidate_preferred_size(gui);
+        self.invalidate_position(gui);
+        if let Some(action_id) = self.action_id() {
+            gui.add_event(Drag(DragPar::done(action_id, value)))
+        }
+    }
+
+    fn fraction_at(&self, bar_layout: &Rectangle, point: &Vector2) -> f32 {
+        match self.orientation.get() {
+            Orientation::Horizontal => Self::horizontal_fraction_at(bar_layout, point),
+            Orientation::Vertical => Self::vertical_fraction_at(bar_layout, point),
+        }
+    }
+
+    fn compute_ticks(&self) {
+        let mut ticks = Vec::new();
+        let mut label_extent = 0.0f32;
+        let value_min = self.value_min.get();
+        let value_max = self.value_max.get();
+
+        if let Some(step) = self.major_tick_step.get() {
+            let minor_count = self.minor_tick_count.get();
+            let minor_step = step / (minor_count + 1) as f32;
+            let major_count = ((value_max - value_min) / step + 1e-4).floor().max(0.0) as u32;
+
+            for major_index in 0..=major_count {
+                let value = value_min + major_index as f32 * step;
+                let (label, label_size) = if self.tick_labels.get() {
+                    let label = self.format_value(value).trim().to_string();
+                    let label_size = self.measure_value(&label);
+                    label_extent = label_extent.max(match self.orientation.get() {
+                        Orientation::Horizontal => label_size.height(),
+                        Orientation::Vertical => label_size.width(),
+                    });
+                    (label, label_size)
+                } else {
+                    (String::new(), Size::empty())
+                };
+                ticks.push(SliderTick { value, major: true, label, label_size });
+
+                for minor_index in 1..=minor_count {
+                    let value = value + minor_index as f32 * minor_step;
+                    if value > value_max {
+                        break;
+                    }
+                    ticks.push(SliderTick { value, major: false, label: String::new(), label_size: Size::empty() });
+                }
+            }
+        }
+
+        let tick_band = if ticks.is_empty() {
+            0.0
+        } else if self.tick_labels.get() {
+            SLIDER_MAJOR_TICK_LENGTH + SLIDER_TICK_LABEL_SPACING + label_extent
+        } else {
+            SLIDER_MAJOR_TICK_LENGTH
+        };
+        self.tick_band.set(tick_band);
+        self.ticks.replace(ticks);
+    }
+
+    fn horizontal_cursor_layout(bar_layout: &Rectangle, fraction: f32) -> Rectangle {
+        let available_width = bar_layout.width - SLIDER_CURSOR_WIDTH;
+        Rectangle::new(
+            bar_layout.x + available_width * fraction,
+            bar_layout.y + SLIDER_CURSOR_SPACING,
+            SLIDER_CURSOR_WIDTH,
+            bar_layout.height - 2.0 * SLIDER_CURSOR_SPACING,
+        )
+    }
+
+    fn vertical_cursor_layout(bar_layout: &Rectangle, fraction: f32) -> Rectangle {
+        let available_height = bar_layout.height - SLIDER_CURSOR_WIDTH;
+        Rectangle::new(
+            bar_layout.x + SLIDER_CURSOR_SPACING,
+            bar_layout.y + available_height * fraction,
+            bar_layout.width - 2.0 * SLIDER_CURSOR_SPACING,
+            SLIDER_CURSOR_WIDTH,
+        )
+    }
+
+    /// The fraction of the bar under the point, the cursor being centered on the point,
+    /// 0 when the cursor fills the bar
+    fn horizontal_fraction_at(bar_layout: &Rectangle, point: &Vector2) -> f32 {
+        let available_width = bar_layout.width - SLIDER_CURSOR_WIDTH;
+        if available_width <= 0.0 {
+            return 0.0;
+        }
+        (point.x - (bar_layout.x + SLIDER_CURSOR_WIDTH * 0.5)) / available_width
+    }
+
+    fn vertical_fraction_at(bar_layout: &Rectangle, point: &Vector2) -> f32 {
+        let available_height = bar_layout.height - SLIDER_CURSOR_WIDTH;
+        if available_height <= 0.0 {
+            return 0.0;
+        }
+        (point.y - (bar_layout.y + SLIDER_CURSOR_WIDTH * 0.5)) / available_height
+    }
+
+    /// The ticks go below a horizontal bar, with their labels centered under the major ones
+    fn render_horizontal_ticks(&self, d: &mut impl RaylibDraw, bar_layout: &Rectangle) {
+        let text_style = self.text_style();
+        let available_width = bar_layout.width - SLIDER_CURSOR_WIDTH;
+        let y = bar_layout.y + bar_layout.height;
+
+        for tick in self.ticks.borrow().iter() {
+            let x = bar_layout.x + SLIDER_CURSOR_WIDTH * 0.5 + available_width * self.value_to_fraction(tick.value);
+            let length = if tick.major { SLIDER_MAJOR_TICK_LENGTH } else { SLIDER_MINOR_TICK_LENGTH };
+            d.draw_line_v(Vector2::new(x, y), Vector2::new(x, y + length), SLIDER_TICK_COLOR);
+
+            if let (true, Some(ts)) = (tick.major && !tick.label.is_empty(), &text_style) {
+                let position = Vector2::new(x - tick.label_size.width() * 0.5, y + SLIDER_MAJOR_TICK_LENGTH + SLIDER_TICK_LABEL_SPACING);
+                ts.draw_text(d, &tick.label, &position);
+            }
+        }
+    }
+
+    /// The ticks go on the right of a vertical bar, with their labels on the right of the major ones
+    fn render_vertical_ticks(&self, d: &mut impl RaylibDraw, bar_layout: &Rectangle) {
+        let text_style = self.text_style();
+        let available_height = bar_layout.height - SLIDER_CURSOR_WIDTH;
+        let x = bar_layout.x + bar_layout.width;
+
+        for tick in self.ticks.borrow().iter() {
+            let y = bar_layout.y + SLIDER_CURSOR_WIDTH * 0.5 + available_height * self.value_to_fraction(tick.value);
+            let length = if tick.major { SLIDER_MAJOR_TICK_LENGTH } else { SLIDER_MINOR_TICK_LENGTH };
+            d.draw_line_v(Vector2::new(x, y), Vector2::new(x + length, y), SLIDER_TICK_COLOR);
+
+            if let (true, Some(ts)) = (tick.major && !tick.label.is_empty(), &text_style) {
+                let position = Vector2::new(x + SLIDER_MAJOR_TICK_LENGTH + SLIDER_TICK_LABEL_SPACING, y - tick.label_size.height() * 0.5);
+                ts.draw_text(d, &tick.label, &position);
+            }
+        }
+    }
+
 }
 
 impl WidgetSpecific for SliderPar {
@@ -121,31 +505,39 @@ impl WidgetSpecific for SliderPar {
         let text_size = self.measure_value(&formatted_value);
         self.value_text.replace(formatted_value);
         self.value_text_size.set(text_size);
-        let bar_size = Size::new(SLIDER_BAR_WIDTH, SLIDER_BAR_HEIGHT);
-        return text_size.max(&bar_size).with_padding(&self.padding());
+        self.compute_ticks();
+        let tick_band = self.tick_band.get();
+        let size = match self.orientation.get() {
+            Orientation::Horizontal => {
+                let bar_size = text_size.max(&Size::new(SLIDER_BAR_WIDTH, SLIDER_BAR_HEIGHT));
+                Size::new(bar_size.width(), bar_size.height() + tick_band)
+            }
+            Orientation::Vertical => {
+                let bar_size = text_size.max(&Size::new(SLIDER_BAR_HEIGHT, SLIDER_BAR_WIDTH));
+                Size::new(bar_size.width() + tick_band, bar_size.height())
+            }
+        };
+        return size.with_padding(&self.padding());
     }
 
-    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {
-        let padding = self.padding();
-        let mut layout = Rectangle::default();
-        layout.width = SLIDER_CURSOR_WIDTH;
-        layout.height = self.widget_height() - padding.v_padding() - 2.0*SLIDER_CURSOR_SPACING;
-        self.cursor_layout.set(layout)
-    }
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
 
     fn compute_child_positions(&self, _gui: &Gui) {
         let content_layout = self.content_layout();
-        let value = self.effective_value();
+        let tick_band = self.tick_band.get();
+        let fraction = self.value_to_fraction(self.effective_value());
 
-        let value_min = self.value_min.get();
-        let value_max = self.value_max.get();
-        let width_minus_cursor_width = content_layout.width - SLIDER_CURSOR_WIDTH;
-        let cursor_position_x = content_layout.x + width_minus_cursor_width * (value-value_min)/(value_max-value_min);
-        let cursor_position_y = content_layout.y + SLIDER_CURSOR_SPACING;
-
-        let mut cursor_layout = self.cursor_layout.get();
-        cursor_layout.x = cursor_position_x;
-        cursor_layout.y = cursor_position_y;
+        let (bar_layout, cursor_layout) = match self.orientation.get() {
+            Orientation::Horizontal => {
+                let bar_layout = Rectangle::new(content_layout.x, content_layout.y, content_layout.width, content_layout.height - tick_band);
+                (bar_layout, Self::horizontal_cursor_layout(&bar_layout, fraction))
+            }
+            Orientation::Vertical => {
+                let bar_layout = Rectangle::new(content_layout.x, content_layout.y, content_layout.width - tick_band, content_layout.height);
+                (bar_layout, Self::vertical_cursor_layout(&bar_layout, fraction))
+            }
+        };
+        self.bar_layout.set(bar_layout);
         self.cursor_layout.set(cursor_layout)
     }
 
@@ -167,14 +559,25 @@ impl WidgetSpecific for SliderPar {
         }
 
 
+        let mut bar_layout = self.bar_layout.get();
+        bar_layout.x += offset.x;
+        bar_layout.y += offset.y;
+
         if drag_info.started() {
-            let mut content_layout = self.content_layout();
-            content_layout.x += offset.x;
-            content_layout.y += offset.y;
             let mouse_position = drag_info.starting_position().clone();
-            let inside= content_layout.check_collision_point_rec(mouse_position);
+            let inside= bar_layout.check_collision_point_rec(mouse_position);
 
-            if inside {
+            let mut cursor_layout = self.cursor_layout.get();
+            cursor_layout.x += offset.x;
+            cursor_layout.y += offset.y;
+            let on_cursor = cursor_layout.check_collision_point_rec(mouse_position);
+
+            if inside && !on_cursor && self.track_click.get() == TrackClick::Page {
+                let value = self.value.get();
+                let toward_value = self.fraction_to_value(self.fraction_at(&bar_layout, &mouse_position));
+                self.change_value(gui, if toward_value > value { self.page_move(value, 1.0).min(toward_value) } else { self.page_move(value, -1.0).max(toward_value) });
+            }
+            else if inside {
                 self.drag_value.set(self.value.get());
                 self.drag_in_progress.set(true);
                 self.drag_starting_position.set(mouse_position);
@@ -182,15 +585,9 @@ impl WidgetSpecific for SliderPar {
         }
         else if drag_info.in_progress() && self.drag_in_progress.get() {
             let mouse_position = drag_info.current_position();
-            let cursor_layout = self.cursor_layout.get();
-            let content_layout = self.content_layout();
-            let value_min = self.value_min.get();
-            let value_max = self.value_max.get();
+            let fraction = self.fraction_at(&bar_layout, mouse_position);
 
-            let available_bar_width = content_layout.width - cursor_layout.width;
-            let displacement = (mouse_position.x - (content_layout.x + offset.x + cursor_layout.width*0.5) )/available_bar_width;
-
-            let value = displacement.clamp(0.0,1.0)*(value_max - value_min) + value_min;
+            let value = self.fraction_to_value(fraction);
             let current_value = self.drag_value.get();
             if current_value != value {
                 self.drag_value.set(value);
@@ -212,17 +609,47 @@ impl WidgetSpecific for SliderPar {
             }
         }
 
+        let wheel_move = mouse_state.wheel_move();
+        if self.get_hoover_state() && wheel_move != 0.0 && !self.drag_in_progress.get() {
+            self.change_value(gui, self.small_move(self.value.get(), wheel_move.signum()));
+        }
+
         self.widget_data.wd_update_action(gui,offset,mouse_state);
     }
 
+    fn update_key_action(&self, gui: &Gui, keyboard_state: &KeyboardState) {
+        if self.drag_in_progress.get() {
+            return;
+        }
+        // the arrows follow the direction of the bar, which is reversed when inverted
+        let direction = if self.inverted.get() { -1.0 } else { 1.0 };
+        for key in keyboard_state.pressed_keys() {
+            let value = self.value.get();
+            match key {
+                KeyboardKey::KEY_RIGHT | KeyboardKey::KEY_UP => self.change_value(gui, self.small_move(value, direction)),
+                KeyboardKey::KEY_LEFT | KeyboardKey::KEY_DOWN => self.change_value(gui, self.small_move(value, -direction)),
+                KeyboardKey::KEY_PAGE_UP => self.change_value(gui, self.page_move(value, 1.0)),
+                KeyboardKey::KEY_PAGE_DOWN => self.change_value(gui, self.page_move(value, -1.0)),
+                KeyboardKey::KEY_HOME => self.change_value(gui, self.value_min.get()),
+                KeyboardKey::KEY_END => self.change_value(gui, self.value_max.get()),
+                _ => {}
+            }
+        }
+    }
+
     fn render_my_visual(&self, _gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
-        let mut content_layout = self.content_layout();
+        let mut content_layout = self.bar_layout.get();
 
         content_layout.x += offset.x;
         content_layout.y += offset.y;
 
         d.draw_rectangle_rec(content_layout, SLIDER_BAR_COLOR);
 
+        match self.orientation.get() {
+            Orientation::Horizontal => self.render_horizontal_ticks(d, &content_layout),
+            Orientation::Vertical => self.render_vertical_ticks(d, &content_layout),
+        }
+
 
         {
             // println!("render offset {:?}",offset);
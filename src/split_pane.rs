@@ -0,0 +1,323 @@
+use std::cell::Cell;
+use std::ops::Deref;
+
+use raylib::prelude::*;
+
+use crate::event::Event::SplitChanged;
+use crate::event::SplitPar;
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::orientation::Orientation;
+use crate::size::Size;
+use crate::widget::Widget;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::{LayoutableWidget, WidgetSpecific};
+
+const SPLIT_DIVIDER_THICKNESS: f32 = 6.0;
+const SPLIT_DIVIDER_COLOR: Color = Color::LIGHTGRAY;
+const SPLIT_DIVIDER_ACTIVE_COLOR: Color = Color::GRAY;
+
+/// How the space is shared between the two sides
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SplitMode {
+    /// the first side gets this part (between 0 and 1) of the space
+    Ratio(f32),
+    /// the first side gets this size in pixels, the second one gets the rest
+    Fixed(f32),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SplitSide {
+    First,
+    Second,
+}
+
+/// A container sharing its space between its first two children, side by side
+/// (horizontal orientation) or one above the other (vertical orientation), separated
+/// by a divider that can be dragged. A double click on the divider collapses the
+/// smaller side, another one restores it.
+pub struct SplitPanePar {
+    widget_data: WidgetData,
+    orientation: Cell<Orientation>,
+    mode: Cell<SplitMode>,
+    first_min_size: Cell<f32>,
+    second_min_size: Cell<f32>,
+    collapsed: Cell<Option<SplitSide>>,
+    /// the sizes of the two sides along the orientation, from the last layout
+    side_sizes: Cell<(f32, f32)>,
+    /// the layout of the divider, in the same coordinates as the content layout
+    divider_layout: Cell<Rectangle>,
+    drag_in_progress: Cell<bool>,
+    drag_starting_size: Cell<f32>,
+}
+
+impl Deref for SplitPanePar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl SplitPanePar {
+    pub fn new() -> Self {
+        Self {
+            widget_data: WidgetData::new(),
+            orientation: Cell::new(Orientation::Horizontal),
+            mode: Cell::new(SplitMode::Ratio(0.5)),
+            first_min_size: Cell::new(0.0),
+            second_min_size: Cell::new(0.0),
+            collapsed: Cell::new(None),
+            side_sizes: Cell::new((0.0, 0.0)),
+            divider_layout: Cell::new(Rectangle::default()),
+            drag_in_progress: Cell::new(false),
+            drag_starting_size: Cell::new(0.0),
+        }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation.get()
+    }
+
+    pub fn set_orientation(&self, gui: &Gui, orientation: Orientation) -> &SplitPanePar {
+        self.orientation.set(orientation);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn split_mode(&self) -> SplitMode {
+        self.mode.get()
+    }
+
+    pub fn set_split_mode(&self, gui: &Gui, mode: SplitMode) -> &SplitPanePar {
+        self.mode.set(mode);
+        self.invalidate_layout(gui);
+        self
+    }
+
+    /// The minimal sizes of the two sides along the orientation. They do not apply to a collapsed side
+    pub fn set_min_sizes(&self, gui: &Gui, first_min_size: f32, second_min_size: f32) -> &SplitPanePar {
+        self.first_min_size.set(first_min_size);
+        self.second_min_size.set(second_min_size);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn collapsed(&self) -> Option<SplitSide> {
+        self.collapsed.get()
+    }
+
+    /// Collapse one side, giving all the space to the other one, or restore the split with None
+    pub fn set_collapsed(&self, gui: &Gui, collapsed: Option<SplitSide>) -> &SplitPanePar {
+        self.collapsed.set(collapsed);
+        self.invalidate_layout(gui);
+        self
+    }
+
+    /// The size of the first side along the orientation, from the last layout
+    pub fn first_size(&self) -> f32 {
+        self.side_sizes.get().0
+    }
+
+    fn invalidate_layout(&self, gui: &Gui) {
+        self.invalidate_content_size(gui);
+        self.invalidate_position(gui);
+    }
+
+    fn along(&self, size: &Size) -> f32 {
+        if self.orientation.get().is_horizontal() { size.width() } else { size.height() }
+    }
+
+    fn across(&self, size: &Size) -> f32 {
+        if self.orientation.get().is_horizontal() { size.height() } else { size.width() }
+    }
+
+    /// Build a size from its dimensions along and across the orientation
+    fn oriented_size(&self, along: f32, across: f32) -> Size {
+        if self.orientation.get().is_horizontal() { Size::new(along, across) } else { Size::new(across, along) }
+    }
+
+    /// The two first children, the only ones laid out
+    fn sides<'a>(&self, gui: &'a Gui) -> (Option<&'a Widget>, Option<&'a Widget>) {
+        let tree_index = match self.get_tree_index() {
+            None => return (None, None),
+            Some(tree_index) => tree_index,
+        };
+        let mut children = gui.get_widget_children(tree_index).filter_map(|idx| gui.get_widget(idx));
+        (children.next(), children.next())
+    }
+
+    /// Share the length available for the sides according to the mode, the min sizes and the collapsed side
+    fn compute_side_sizes(&self, length: f32) -> (f32, f32) {
+        let first = match (self.collapsed.get(), self.mode.get()) {
+            (Some(SplitSide::First), _) => 0.0,
+            (Some(SplitSide::Second), _) => length,
+            (None, SplitMode::Ratio(ratio)) => length * ratio.clamp(0.0, 1.0),
+            (None, SplitMode::Fixed(size)) => size,
+        };
+        let first = if self.collapsed.get().is_some() {
+            first
+        } else {
+            let max = (length - self.second_min_size.get()).max(0.0);
+            first.min(max).max(self.first_min_size.get().min(length))
+        };
+        (first, (length - first).max(0.0))
+    }
+
+    /// Move the divider so that the first side gets the provided size
+    fn set_first_size(&self, gui: &Gui, first_size: f32) -> bool {
+        let (first, second) = self.side_sizes.get();
+        let length = first + second;
+        let first_size = first_size.clamp(0.0, length);
+        let mode = match self.mode.get() {
+            SplitMode::Ratio(_) => SplitMode::Ratio(if length > 0.0 { first_size / length } else { 0.5 }),
+            SplitMode::Fixed(_) => SplitMode::Fixed(first_size),
+        };
+        if mode == self.mode.get() && self.collapsed.get().is_none() {
+            return false;
+        }
+        self.mode.set(mode);
+        self.collapsed.set(None);
+        self.invalidate_layout(gui);
+        true
+    }
+
+    fn fire_split_event(&self, gui: &Gui, in_progress: bool) {
+        if let Some(action_id) = self.action_id() {
+            let (first, second) = self.compute_side_sizes(self.side_sizes.get().0 + self.side_sizes.get().1);
+            let length = first + second;
+            let ratio = if length > 0.0 { first / length } else { 0.0 };
+            gui.add_event(SplitChanged(SplitPar::new(&action_id, first, ratio, self.collapsed.get(), in_progress)))
+        }
+    }
+}
+
+impl WidgetSpecific for SplitPanePar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, gui: &Gui) -> Size {
+        if let Some(tree_index) = self.get_tree_index() {
+            // only the two first children are displayed
+            for (position, child_index) in gui.get_widget_children(tree_index).enumerate() {
+                if let Some(w) = gui.get_widget(child_index) {
+                    w.set_visible(position < 2);
+                }
+            }
+        }
+
+        let (first, second) = self.sides(gui);
+        let first_size = first.map_or(Size::empty(), |w| w.compute_computed_size(gui));
+        let second_size = second.map_or(Size::empty(), |w| w.compute_computed_size(gui));
+
+        let along = self.along(&first_size).max(self.first_min_size.get())
+            + self.along(&second_size).max(self.second_min_size.get())
+            + SPLIT_DIVIDER_THICKNESS;
+        let across = self.across(&first_size).max(self.across(&second_size));
+
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&self.oriented_size(along, across).with_padding(&self.padding()));
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, gui: &Gui, available_size: Size) {
+        let available_size = available_size.without_padding(&self.padding());
+        let length = (self.along(&available_size) - SPLIT_DIVIDER_THICKNESS).max(0.0);
+        let across = self.across(&available_size);
+        let (first_length, second_length) = self.compute_side_sizes(length);
+        self.side_sizes.set((first_length, second_length));
+
+        let (first, second) = self.sides(gui);
+        if let Some(w) = first {
+            w.set_visible(self.collapsed.get() != Some(SplitSide::First));
+            w.update_content_size(gui, &self.oriented_size(first_length, across));
+        }
+        if let Some(w) = second {
+            w.set_visible(self.collapsed.get() != Some(SplitSide::Second));
+            w.update_content_size(gui, &self.oriented_size(second_length, across));
+        }
+    }
+
+    fn compute_child_positions(&self, gui: &Gui) {
+        let content_layout = self.content_layout();
+        let content_size = Size::new(content_layout.width, content_layout.height);
+        let across = self.across(&content_size);
+        let (first_length, second_length) = self.side_sizes.get();
+        let horizontal = self.orientation.get().is_horizontal();
+
+        let divider_layout = if horizontal {
+            Rectangle::new(content_layout.x + first_length, content_layout.y, SPLIT_DIVIDER_THICKNESS, across)
+        } else {
+            Rectangle::new(content_layout.x, content_layout.y + first_length, across, SPLIT_DIVIDER_THICKNESS)
+        };
+        self.divider_layout.set(divider_layout);
+
+        let (first, second) = self.sides(gui);
+        if let Some(w) = first {
+            w.compute_default_target(&self.oriented_size(first_length, across));
+            w.update_child_positions(gui);
+        }
+        if let Some(w) = second {
+            let shift = first_length + SPLIT_DIVIDER_THICKNESS;
+            w.compute_default_target(&self.oriented_size(second_length, across));
+            let widget_layout = w.widget_layout();
+            let target = if horizontal {
+                Vector2::new(widget_layout.x + shift, widget_layout.y)
+            } else {
+                Vector2::new(widget_layout.x, widget_layout.y + shift)
+            };
+            w.set_widget_target(&target);
+            w.update_child_positions(gui);
+        }
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        let mut divider_layout = self.divider_layout.get();
+        divider_layout.x += offset.x;
+        divider_layout.y += offset.y;
+
+        let drag_info = mouse_state.drag_info();
+
+        if mouse_state.is_double_clicked() && divider_layout.check_collision_point_rec(mouse_state.mouse_position()) {
+            self.drag_in_progress.set(false);
+            let collapsed = match self.collapsed.get() {
+                Some(_) => None,
+                None => {
+                    let (first, second) = self.side_sizes.get();
+                    Some(if first <= second { SplitSide::First } else { SplitSide::Second })
+                }
+            };
+            self.set_collapsed(gui, collapsed);
+            self.fire_split_event(gui, false);
+        } else if drag_info.started() {
+            if divider_layout.check_collision_point_rec(drag_info.starting_position()) {
+                self.drag_in_progress.set(true);
+                self.drag_starting_size.set(self.side_sizes.get().0);
+            }
+        } else if drag_info.in_progress() && self.drag_in_progress.get() {
+            let delta = *drag_info.delta();
+            let displacement = if self.orientation.get().is_horizontal() { delta.x } else { delta.y };
+            if self.set_first_size(gui, self.drag_starting_size.get() + displacement) {
+                self.fire_split_event(gui, true);
+            }
+        } else if drag_info.done() && self.drag_in_progress.get() {
+            self.drag_in_progress.set(false);
+            self.fire_split_event(gui, false);
+        }
+
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, _gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let mut divider_layout = self.divider_layout.get();
+        divider_layout.x += offset.x;
+        divider_layout.y += offset.y;
+        let color = if self.drag_in_progress.get() { SPLIT_DIVIDER_ACTIVE_COLOR } else { SPLIT_DIVIDER_COLOR };
+        d.draw_rectangle_rec(divider_layout, color);
+    }
+}
@@ -0,0 +1,115 @@
+use raylib::prelude::*;
+use std::ops::Deref;
+
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::{LayoutableWidget, WidgetSpecific};
+
+/// A container layering all its children over its content. Each child fills the content
+/// or is aligned in it through its own fill and alignment settings, its position is ignored.
+/// Children are drawn by increasing z-index, and only the topmost one under the mouse is hoovered
+pub struct StackPar {
+    widget_data: WidgetData,
+}
+
+impl Deref for StackPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl StackPar {
+    pub fn new() -> Self {
+        let stack_par = Self { widget_data: WidgetData::new() };
+        stack_par.model.layered_children.set(true);
+        stack_par
+    }
+}
+
+impl WidgetSpecific for StackPar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, gui: &Gui) -> Size {
+        let tree_index = match self.get_tree_index() {
+            None => return Size::empty(),
+            Some(tree_index) => tree_index,
+        };
+
+        let mut max_size = Size::empty();
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(w) = gui.get_widget(child_index) {
+                max_size.max_mut(&w.compute_computed_size(gui));
+            }
+        }
+
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&max_size.with_padding(&self.padding()));
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, gui: &Gui, available_size: Size) {
+        let tree_index = match self.get_tree_index() {
+            None => return,
+            Some(tree_index) => tree_index,
+        };
+
+        let available_size_for_children = available_size.without_padding(&self.padding());
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(w) = gui.get_widget(child_index) {
+                w.update_content_size(gui, &available_size_for_children);
+            }
+        }
+    }
+
+    fn compute_child_positions(&self, gui: &Gui) {
+        let tree_index = match self.get_tree_index() {
+            None => return,
+            Some(tree_index) => tree_index,
+        };
+
+        let content_layout = self.content_layout();
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(w) = gui.get_widget(child_index) {
+                let alignment = w.alignment();
+                let position = Vector2::new(
+                    -(content_layout.width - w.widget_width()) * alignment.horizontal.shift_factor(),
+                    -(content_layout.height - w.widget_height()) * alignment.vertical.shift_factor(),
+                );
+                w.set_widget_target(&position);
+                w.update_child_positions(gui);
+            }
+        }
+    }
+
+    fn height_for_width(&self, gui: &Gui, width: f32) -> Option<f32> {
+        let tree_index = self.get_tree_index()?;
+        let padding = self.padding();
+        let content_width = width - padding.h_padding();
+
+        let mut max_height: f32 = 0.0;
+        for child_index in gui.get_widget_children(tree_index) {
+            if let Some(w) = gui.get_widget(child_index) {
+                let child_width = if w.fill_width_enabled() { content_width } else { w.compute_computed_size(gui).width().min(content_width) };
+                max_height = max_height.max(w.compute_height_for_width(gui, child_width));
+            }
+        }
+
+        Some(self.preferred_size().height().max(max_height + padding.v_padding()))
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, _gui: &Gui, _d: &mut impl RaylibDraw, _offset: &Vector2) {}
+}
@@ -0,0 +1,374 @@
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+
+use raylib::prelude::*;
+use generational_arena::Index;
+
+use crate::event::Event::{TabChanged, TabCloseRequested};
+use crate::event::TabPar;
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::size::Size;
+use crate::widget::Widget;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::{LayoutableWidget, WidgetSpecific};
+
+const TAB_MARGIN: f32 = 6.0;
+const TAB_SPACING: f32 = 2.0;
+const TAB_MIN_HEADER_HEIGHT: f32 = 24.0;
+const TAB_CLOSE_SIZE: f32 = 10.0;
+const TAB_ACTIVE_COLOR: Color = Color::RAYWHITE;
+const TAB_INACTIVE_COLOR: Color = Color::LIGHTGRAY;
+const TAB_HOOVERED_COLOR: Color = Color::SKYBLUE;
+const TAB_BORDER_COLOR: Color = Color::GRAY;
+const TAB_CLOSE_COLOR: Color = Color::DARKGRAY;
+
+struct Tab {
+    child: Index,
+    title: String,
+    closable: bool,
+    title_size: Size,
+    /// the layout of the tab in the header, in the same coordinates as the content layout
+    layout: Rectangle,
+}
+
+impl Tab {
+    fn new(child: Index, title: String) -> Self {
+        Self { child, title, closable: false, title_size: Size::empty(), layout: Rectangle::default() }
+    }
+
+    fn width(&self) -> f32 {
+        let close_width = if self.closable { TAB_CLOSE_SIZE + TAB_MARGIN } else { 0.0 };
+        self.title_size.width() + close_width + 2.0 * TAB_MARGIN
+    }
+
+    fn close_layout(&self) -> Rectangle {
+        Rectangle::new(
+            self.layout.x + self.layout.width - TAB_MARGIN - TAB_CLOSE_SIZE,
+            self.layout.y + (self.layout.height - TAB_CLOSE_SIZE) * 0.5,
+            TAB_CLOSE_SIZE,
+            TAB_CLOSE_SIZE,
+        )
+    }
+}
+
+/// A container showing one child at a time, selected with a strip of tabs.
+/// Every child of the pane gets a tab, in the order of the children unless
+/// the tabs are reordered by dragging. Only the active child is laid out and
+/// rendered, the other ones are hidden but kept in the tree with their state.
+pub struct TabPanePar {
+    widget_data: WidgetData,
+    /// the tabs in display order
+    tabs: RefCell<Vec<Tab>>,
+    active_tab: Cell<Option<Index>>,
+    header_height: Cell<f32>,
+    hoovered_tab: Cell<Option<usize>>,
+    /// the display position of the tab being dragged
+    dragged_tab: Cell<Option<usize>>,
+}
+
+impl Deref for TabPanePar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+impl TabPanePar {
+    pub fn new() -> Self {
+        Self {
+            widget_data: WidgetData::new(),
+            tabs: RefCell::new(Vec::new()),
+            active_tab: Cell::new(None),
+            header_height: Cell::new(0.0),
+            hoovered_tab: Cell::new(None),
+            dragged_tab: Cell::new(None),
+        }
+    }
+
+    /// The children of the pane, in the display order of their tabs
+    pub fn tab_children(&self, gui: &Gui) -> Vec<Index> {
+        self.sync_tabs(gui);
+        self.tabs.borrow().iter().map(|t| t.child).collect()
+    }
+
+    pub fn tab_title(&self, gui: &Gui, child: Index) -> Option<String> {
+        self.sync_tabs(gui);
+        self.tabs.borrow().iter().find(|t| t.child == child).map(|t| t.title.clone())
+    }
+
+    pub fn set_tab_title(&self, gui: &Gui, child: Index, title: &str) -> &TabPanePar {
+        self.sync_tabs(gui);
+        if let Some(tab) = self.tabs.borrow_mut().iter_mut().find(|t| t.child == child) {
+            tab.title = title.to_string();
+        }
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// A closable tab shows a close button, which emits a `TabCloseRequested` event.
+    /// Removing the child is left to the application
+    pub fn set_tab_closable(&self, gui: &Gui, child: Index, closable: bool) -> &TabPanePar {
+        self.sync_tabs(gui);
+        if let Some(tab) = self.tabs.borrow_mut().iter_mut().find(|t| t.child == child) {
+            tab.closable = closable;
+        }
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn active_tab(&self, gui: &Gui) -> Option<Index> {
+        self.sync_tabs(gui);
+        self.active_tab.get()
+    }
+
+    pub fn set_active_tab(&self, gui: &Gui, child: Index) -> &TabPanePar {
+        self.sync_tabs(gui);
+        if self.tabs.borrow().iter().any(|t| t.child == child) && self.active_tab.get() != Some(child) {
+            self.active_tab.set(Some(child));
+            self.sync_tabs(gui);
+            self.invalidate_preferred_size(gui);
+            self.invalidate_content_size(gui);
+        }
+        self
+    }
+
+    /// Keep the tabs consistent with the children of the pane : new children get a tab
+    /// at the end of the strip, the tabs of removed children are dropped and only the
+    /// active child is visible
+    fn sync_tabs(&self, gui: &Gui) {
+        let tree_index = match self.get_tree_index() {
+            None => return,
+            Some(tree_index) => tree_index,
+        };
+        let children: Vec<Index> = gui.get_widget_children(tree_index).collect();
+
+        let mut tabs = self.tabs.borrow_mut();
+        tabs.retain(|t| children.contains(&t.child));
+        for child in children.iter() {
+            if !tabs.iter().any(|t| t.child == *child) {
+                let title = gui.get_widget(*child).and_then(|w| w.action_id()).unwrap_or_default();
+                tabs.push(Tab::new(*child, title));
+            }
+        }
+
+        let active_tab = match self.active_tab.get() {
+            Some(active) if tabs.iter().any(|t| t.child == active) => Some(active),
+            _ => tabs.first().map(|t| t.child),
+        };
+        self.active_tab.set(active_tab);
+
+        for child in children {
+            if let Some(w) = gui.get_widget(child) {
+                w.set_visible(Some(child) == active_tab);
+            }
+        }
+    }
+
+    fn active_widget<'a>(&self, gui: &'a Gui) -> Option<&'a Widget> {
+        self.active_tab.get().and_then(|child| gui.get_widget(child))
+    }
+
+    fn body_size(&self) -> Size {
+        let content_layout = self.content_layout();
+        Size::new(content_layout.width, (content_layout.height - self.header_height.get()).max(0.0))
+    }
+
+    /// The display position of the tab under the point, and whether the point is on its close button
+    fn tab_at(&self, offset: &Vector2, point: &Vector2) -> Option<(usize, bool)> {
+        let local = Vector2::new(point.x - offset.x, point.y - offset.y);
+        let tabs = self.tabs.borrow();
+        tabs.iter().position(|t| t.layout.check_collision_point_rec(local)).map(|position| {
+            let tab = &tabs[position];
+            (position, tab.closable && tab.close_layout().check_collision_point_rec(local))
+        })
+    }
+
+    /// The display position the dragged tab should take for the mouse abscissa, comparing
+    /// it with the middle of the other tabs as if the dragged one was not in the strip
+    fn drop_position(&self, offset: &Vector2, dragged: usize, mouse_x: f32) -> usize {
+        let tabs = self.tabs.borrow();
+        let mut x = self.content_layout().x + offset.x;
+        let mut position = 0;
+        for (i, tab) in tabs.iter().enumerate() {
+            if i == dragged {
+                continue;
+            }
+            if x + tab.layout.width * 0.5 < mouse_x {
+                position += 1;
+            }
+            x += tab.layout.width + TAB_SPACING;
+        }
+        position
+    }
+
+    fn emit_tab_event(&self, gui: &Gui, position: usize, close_requested: bool) {
+        if let Some(action_id) = self.action_id() {
+            let child = self.tabs.borrow()[position].child;
+            let par = TabPar::new(&action_id, position, child);
+            gui.add_event(if close_requested { TabCloseRequested(par) } else { TabChanged(par) });
+        }
+    }
+}
+
+impl WidgetSpecific for TabPanePar {
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, gui: &Gui) -> Size {
+        self.sync_tabs(gui);
+
+        let mut header_width: f32 = 0.0;
+        let mut title_height: f32 = 0.0;
+        {
+            let text_style = self.text_style();
+            let mut tabs = self.tabs.borrow_mut();
+            for tab in tabs.iter_mut() {
+                tab.title_size = match &text_style {
+                    None => Size::empty(),
+                    Some(ts) => ts.measure_text(&tab.title),
+                };
+                title_height = title_height.max(tab.title_size.height());
+                header_width += tab.width() + TAB_SPACING;
+            }
+        }
+        let header_height = (title_height + 2.0 * TAB_MARGIN).max(TAB_MIN_HEADER_HEIGHT);
+        self.header_height.set(header_height);
+
+        let active_size = match self.active_widget(gui) {
+            None => Size::empty(),
+            Some(w) => w.compute_computed_size(gui),
+        };
+
+        let size = Size::new(active_size.width().max(header_width), active_size.height() + header_height)
+            .with_padding(&self.padding());
+
+        let mut user_preferred_size = self.preferred_size();
+        user_preferred_size.replace_empty_dimensions_and_max(&size);
+        user_preferred_size
+    }
+
+    fn compute_child_content_size(&self, gui: &Gui, available_size: Size) {
+        let available_size_for_child = available_size.without_padding(&self.padding());
+        let available_size_for_child =
+            available_size_for_child.with_height((available_size_for_child.height() - self.header_height.get()).max(0.0));
+
+        if let Some(w) = self.active_widget(gui) {
+            w.update_content_size(gui, &available_size_for_child);
+        }
+    }
+
+    fn compute_child_positions(&self, gui: &Gui) {
+        let content_layout = self.content_layout();
+        let header_height = self.header_height.get();
+        {
+            let mut x = content_layout.x;
+            let mut tabs = self.tabs.borrow_mut();
+            for tab in tabs.iter_mut() {
+                let width = tab.width();
+                tab.layout = Rectangle::new(x, content_layout.y, width, header_height);
+                x += width + TAB_SPACING;
+            }
+        }
+
+        if let Some(w) = self.active_widget(gui) {
+            w.compute_default_target(&self.body_size());
+            let widget_layout = w.widget_layout();
+            w.set_widget_target(&Vector2::new(widget_layout.x, widget_layout.y + header_height));
+            w.update_child_positions(gui)
+        }
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        let hoovered_tab = if self.get_hoover_state() {
+            self.tab_at(offset, mouse_state.mouse_position()).map(|(position, _)| position)
+        } else {
+            None
+        };
+        self.hoovered_tab.set(hoovered_tab);
+
+        let drag_info = mouse_state.drag_info();
+        if drag_info.started() {
+            if let Some((position, on_close)) = self.tab_at(offset, drag_info.starting_position()) {
+                if on_close {
+                    self.emit_tab_event(gui, position, true);
+                } else {
+                    let child = self.tabs.borrow()[position].child;
+                    if self.active_tab.get() != Some(child) {
+                        self.set_active_tab(gui, child);
+                        self.emit_tab_event(gui, position, false);
+                    }
+                    self.dragged_tab.set(Some(position));
+                }
+            }
+        } else if drag_info.in_progress() {
+            if let Some(dragged) = self.dragged_tab.get() {
+                let position = self.drop_position(offset, dragged, drag_info.current_position().x);
+                if position != dragged {
+                    let mut tabs = self.tabs.borrow_mut();
+                    let tab = tabs.remove(dragged);
+                    tabs.insert(position, tab);
+                    self.dragged_tab.set(Some(position));
+                    self.invalidate_position(gui);
+                }
+            }
+        } else if drag_info.done() {
+            self.dragged_tab.set(None);
+        }
+
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, _gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let content_layout = self.content_layout();
+        let header_bottom = content_layout.y + offset.y + self.header_height.get();
+        let active_tab = self.active_tab.get();
+        let hoovered_tab = self.hoovered_tab.get();
+        let text_style = self.text_style();
+
+        for (position, tab) in self.tabs.borrow().iter().enumerate() {
+            let mut layout = tab.layout;
+            layout.x += offset.x;
+            layout.y += offset.y;
+
+            let color = if Some(tab.child) == active_tab {
+                TAB_ACTIVE_COLOR
+            } else if Some(position) == hoovered_tab {
+                TAB_HOOVERED_COLOR
+            } else {
+                TAB_INACTIVE_COLOR
+            };
+            d.draw_rectangle_rec(layout, color);
+            d.draw_rectangle_lines_ex(layout, 1, TAB_BORDER_COLOR);
+
+            if let Some(ts) = &text_style {
+                let position = Vector2::new(layout.x + TAB_MARGIN, layout.y + (layout.height - tab.title_size.height()) * 0.5);
+                ts.draw_text(d, &tab.title, &position);
+            }
+
+            if tab.closable {
+                let mut close_layout = tab.close_layout();
+                close_layout.x += offset.x;
+                close_layout.y += offset.y;
+                let top_left = Vector2::new(close_layout.x, close_layout.y);
+                let bottom_right = Vector2::new(close_layout.x + close_layout.width, close_layout.y + close_layout.height);
+                let top_right = Vector2::new(close_layout.x + close_layout.width, close_layout.y);
+                let bottom_left = Vector2::new(close_layout.x, close_layout.y + close_layout.height);
+                d.draw_line_v(top_left, bottom_right, TAB_CLOSE_COLOR);
+                d.draw_line_v(top_right, bottom_left, TAB_CLOSE_COLOR);
+            }
+        }
+
+        d.draw_line_v(
+            Vector2::new(content_layout.x + offset.x, header_bottom),
+            Vector2::new(content_layout.x + offset.x + content_layout.width, header_bottom),
+            TAB_BORDER_COLOR,
+        );
+    }
+}
@@ -0,0 +1,632 @@
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::ops::Deref;
+
+use raylib::prelude::*;
+
+use crate::event::Event::{Select, Sort};
+use crate::event::{SelectPar, SortPar};
+use crate::gui::Gui;
+use crate::mouse::MouseState;
+use crate::orientation::Orientation::{Horizontal, Vertical};
+use crate::scroll_bar::{ScrollBar, SCROLL_BAR_THICKNESS};
+use crate::selection::{Selection, SelectionMode};
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+/// The width of a column of a table
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ColumnWidth {
+    /// fixed width in pixels
+    Fixed(f32),
+    /// share of the width left by the other columns, like `Fill::Enabled`
+    Weighted { weight: u32 },
+    /// fit the header and the cells displayed so far
+    Auto,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    pub fn reversed(&self) -> SortOrder {
+        match self {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        }
+    }
+}
+
+/// Build the text of a cell from the (model) row index and the column index
+pub type CellBuilder = Box<dyn Fn(usize, usize) -> String>;
+/// Compare two (model) rows on the provided column : `compare(column, row_a, row_b)`
+pub type RowComparator = Box<dyn Fn(usize, usize, usize) -> Ordering>;
+
+struct TableColumn {
+    title: String,
+    title_size: Size,
+    width: ColumnWidth,
+    /// widest content measured, used by `ColumnWidth::Auto`
+    auto_width: f32,
+    /// width computed during the last layout
+    computed_width: f32,
+    /// position of the column relative to the start of the first column
+    x: f32,
+}
+
+struct VisibleCell {
+    text: String,
+    text_size: Size,
+}
+
+/// A row currently displayed by the table
+struct VisibleRow {
+    /// the index of the row in the model
+    row: usize,
+    /// the position of the row, relative to the top of the body
+    y: f32,
+    cells: Vec<VisibleCell>,
+}
+
+/// A table that displays cells obtained from a cell builder callback. Only the
+/// visible rows are built. The columns can be resized by dragging the right
+/// edge of their header and sorted by clicking on their header.
+pub struct TablePar {
+    widget_data: WidgetData,
+    columns: RefCell<Vec<TableColumn>>,
+    row_count: Cell<usize>,
+    row_height: Cell<f32>,
+    header_height: Cell<f32>,
+    cell_builder: RefCell<Option<CellBuilder>>,
+    row_comparator: RefCell<Option<RowComparator>>,
+    /// model row index of each displayed row
+    row_order: RefCell<Vec<usize>>,
+    row_order_valid: Cell<bool>,
+    sort: Cell<Option<(usize, SortOrder)>>,
+    visible_rows: RefCell<Vec<VisibleRow>>,
+    hoovered_row: Cell<Option<usize>>,
+    selection: RefCell<Selection>,
+    /// the column being resized and its width when the drag started
+    resized_column: Cell<Option<(usize, f32)>>,
+    /// the column whose header was pressed
+    pressed_header: Cell<Option<usize>>,
+    body_layout: Cell<Rectangle>,
+    vertical_bar: ScrollBar,
+    horizontal_bar: ScrollBar,
+}
+
+impl Deref for TablePar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+const TABLE_DEFAULT_WIDTH: f32 = 300.0;
+const TABLE_DEFAULT_HEIGHT: f32 = 200.0;
+const TABLE_DEFAULT_ROW_HEIGHT: f32 = 30.0;
+const TABLE_MIN_COLUMN_WIDTH: f32 = 20.0;
+const TABLE_CELL_MARGIN: f32 = 4.0;
+const TABLE_RESIZE_HANDLE_WIDTH: f32 = 4.0;
+const TABLE_SORT_INDICATOR_SIZE: f32 = 8.0;
+const TABLE_WHEEL_STEP: f32 = 40.0;
+const TABLE_HEADER_COLOR: Color = Color::LIGHTGRAY;
+const TABLE_GRID_COLOR: Color = Color::GRAY;
+const TABLE_SORT_INDICATOR_COLOR: Color = Color::DARKGRAY;
+const TABLE_SELECTED_COLOR: Color = Color::SKYBLUE;
+const TABLE_HOOVERED_COLOR: Color = Color::LIGHTGRAY;
+
+impl TablePar {
+    pub fn new() -> Self {
+        let table_par = Self {
+            widget_data: WidgetData::new(),
+            columns: RefCell::new(Vec::new()),
+            row_count: Cell::new(0),
+            row_height: Cell::new(TABLE_DEFAULT_ROW_HEIGHT),
+            header_height: Cell::new(TABLE_DEFAULT_ROW_HEIGHT),
+            cell_builder: RefCell::new(None),
+            row_comparator: RefCell::new(None),
+            row_order: RefCell::new(Vec::new()),
+            row_order_valid: Cell::new(false),
+            sort: Cell::new(None),
+            visible_rows: RefCell::new(Vec::new()),
+            hoovered_row: Cell::new(None),
+            selection: RefCell::new(Selection::new(SelectionMode::Single)),
+            resized_column: Cell::new(None),
+            pressed_header: Cell::new(None),
+            body_layout: Cell::new(Rectangle::default()),
+            vertical_bar: ScrollBar::new(Vertical),
+            horizontal_bar: ScrollBar::new(Horizontal),
+        };
+        table_par.set_hooverable(true);
+        table_par
+    }
+
+    pub fn add_column(&self, gui: &Gui, title: &str, width: ColumnWidth) -> &TablePar {
+        self.columns.borrow_mut().push(TableColumn {
+            title: title.to_string(),
+            title_size: Size::empty(),
+            width,
+            auto_width: 0.0,
+            computed_width: 0.0,
+            x: 0.0,
+        });
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.columns.borrow().len()
+    }
+
+    pub fn column_width(&self, column: usize) -> Option<ColumnWidth> {
+        self.columns.borrow().get(column).map(|c| c.width)
+    }
+
+    pub fn set_column_width(&self, gui: &Gui, column: usize, width: ColumnWidth) -> &TablePar {
+        if let Some(c) = self.columns.borrow_mut().get_mut(column) {
+            c.width = width;
+        }
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count.get()
+    }
+
+    pub fn set_row_count(&self, gui: &Gui, row_count: usize) -> &TablePar {
+        if row_count == self.row_count.get() {
+            return self;
+        }
+        self.row_count.set(row_count);
+        self.row_order_valid.set(false);
+        if self.selection.borrow_mut().truncate(row_count) {
+            self.fire_selection_event(gui);
+        }
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn set_row_height(&self, gui: &Gui, row_height: f32) -> &TablePar {
+        self.row_height.set(row_height.max(1.0));
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn set_cell_builder(&self, gui: &Gui, cell_builder: impl Fn(usize, usize) -> String + 'static) -> &TablePar {
+        self.cell_builder.replace(Some(Box::new(cell_builder)));
+        self.invalidate_position(gui);
+        self
+    }
+
+    /// Set the comparator used to sort the rows when a header is clicked. Without
+    /// comparator, the table only updates its sort indicator and fires a sort event.
+    pub fn set_row_comparator(&self, gui: &Gui, row_comparator: impl Fn(usize, usize, usize) -> Ordering + 'static) -> &TablePar {
+        self.row_comparator.replace(Some(Box::new(row_comparator)));
+        self.row_order_valid.set(false);
+        self.invalidate_position(gui);
+        self
+    }
+
+    /// Rebuild the visible rows and sort again, to use when the data of the model changed.
+    pub fn refresh(&self, gui: &Gui) -> &TablePar {
+        self.row_order_valid.set(false);
+        for column in self.columns.borrow_mut().iter_mut() {
+            column.auto_width = 0.0;
+        }
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn sort_state(&self) -> Option<(usize, SortOrder)> {
+        self.sort.get()
+    }
+
+    pub fn sort_by(&self, gui: &Gui, column: usize, order: SortOrder) -> &TablePar {
+        if column >= self.column_count() || self.sort.get() == Some((column, order)) {
+            return self;
+        }
+        self.sort.set(Some((column, order)));
+        self.row_order_valid.set(false);
+        self.invalidate_position(gui);
+        if let Some(action_id) = self.action_id() {
+            gui.add_event(Sort(SortPar::new(&action_id, column, order)))
+        }
+        self
+    }
+
+    pub fn set_selection_mode(&self, gui: &Gui, selection_mode: SelectionMode) -> &TablePar {
+        if self.selection.borrow_mut().set_mode(selection_mode) {
+            self.fire_selection_event(gui);
+        }
+        self
+    }
+
+    /// The model indices of the selected rows
+    pub fn selected_rows(&self) -> Vec<usize> {
+        self.selection.borrow().selected()
+    }
+
+    pub fn select_row(&self, gui: &Gui, row: usize) -> &TablePar {
+        if row < self.row_count.get() && self.selection.borrow_mut().select(row) {
+            self.fire_selection_event(gui);
+        }
+        self
+    }
+
+    pub fn clear_selection(&self, gui: &Gui) -> &TablePar {
+        if self.selection.borrow_mut().clear() {
+            self.fire_selection_event(gui);
+        }
+        self
+    }
+
+    fn fire_selection_event(&self, gui: &Gui) {
+        if let Some(action_id) = self.action_id() {
+            gui.add_event(Select(SelectPar::new(&action_id, self.selected_rows())))
+        }
+    }
+
+    fn measure(&self, text: &str) -> Size {
+        match self.text_style() {
+            None => Size::empty(),
+            Some(ts) => ts.measure_text(text)
+        }
+    }
+
+    fn ensure_row_order(&self) {
+        if self.row_order_valid.get() {
+            return;
+        }
+        let mut row_order = self.row_order.borrow_mut();
+        row_order.clear();
+        row_order.extend(0..self.row_count.get());
+
+        if let (Some((column, order)), Some(comparator)) = (self.sort.get(), self.row_comparator.borrow().as_ref()) {
+            row_order.sort_by(|row_a, row_b| {
+                let ordering = comparator(column, *row_a, *row_b);
+                if order == SortOrder::Descending { ordering.reverse() } else { ordering }
+            });
+        }
+        self.row_order_valid.set(true);
+    }
+
+    fn columns_width(&self) -> f32 {
+        self.columns.borrow().iter().map(|c| c.computed_width).sum()
+    }
+
+    /// Compute the width and the position of each column. The weighted columns
+    /// share the width left by the fixed and auto columns
+    fn layout_columns(&self, available_width: f32) {
+        let mut columns = self.columns.borrow_mut();
+
+        let mut summed_fixed_width: f32 = 0.0;
+        let mut summed_weight: u32 = 0;
+        for column in columns.iter() {
+            match column.width {
+                ColumnWidth::Fixed(width) => summed_fixed_width += width.max(TABLE_MIN_COLUMN_WIDTH),
+                ColumnWidth::Auto => summed_fixed_width += TablePar::auto_column_width(column),
+                ColumnWidth::Weighted { weight } => summed_weight += weight,
+            }
+        }
+
+        let weight_width = (available_width - summed_fixed_width).max(0.0) / (summed_weight.max(1) as f32);
+
+        let mut x = 0.0;
+        for column in columns.iter_mut() {
+            let width = match column.width {
+                ColumnWidth::Fixed(width) => width,
+                ColumnWidth::Auto => TablePar::auto_column_width(column),
+                ColumnWidth::Weighted { weight } => weight_width * weight as f32,
+            };
+            column.computed_width = width.max(TABLE_MIN_COLUMN_WIDTH);
+            column.x = x;
+            x += column.computed_width;
+        }
+    }
+
+    fn auto_column_width(column: &TableColumn) -> f32 {
+        column.auto_width.max(column.title_size.width() + TABLE_SORT_INDICATOR_SIZE + TABLE_CELL_MARGIN) + 2.0 * TABLE_CELL_MARGIN
+    }
+
+    /// Build the visible rows and returns true if an auto column became wider
+    fn update_visible_rows(&self) -> bool {
+        self.ensure_row_order();
+        let body_layout = self.body_layout.get();
+        let row_height = self.row_height.get();
+        let scroll = self.vertical_bar.value();
+
+        let mut visible_rows = Vec::new();
+        let mut auto_width_changed = false;
+
+        if let Some(cell_builder) = self.cell_builder.borrow().as_ref() {
+            let row_order = self.row_order.borrow();
+            let mut columns = self.columns.borrow_mut();
+            let mut view_row = (scroll / row_height) as usize;
+            while view_row < row_order.len() {
+                let y = view_row as f32 * row_height - scroll;
+                if y >= body_layout.height {
+                    break;
+                }
+                let row = row_order[view_row];
+                let mut cells = Vec::with_capacity(columns.len());
+                for (column_index, column) in columns.iter_mut().enumerate() {
+                    let text = cell_builder(row, column_index);
+                    let text_size = self.measure(&text);
+                    if column.width == ColumnWidth::Auto && text_size.width() > column.auto_width {
+                        column.auto_width = text_size.width();
+                        auto_width_changed = true;
+                    }
+                    cells.push(VisibleCell { text, text_size });
+                }
+                visible_rows.push(VisibleRow { row, y, cells });
+                view_row += 1;
+            }
+        }
+        self.visible_rows.replace(visible_rows);
+        auto_width_changed
+    }
+
+    fn header_layout(&self) -> Rectangle {
+        let body_layout = self.body_layout.get();
+        Rectangle::new(body_layout.x, body_layout.y - self.header_height.get(), body_layout.width, self.header_height.get())
+    }
+
+    /// the column whose header contains the position, and true if the position is on its resize handle
+    fn header_at(&self, offset: &Vector2, position: &Vector2) -> Option<(usize, bool)> {
+        let mut header_layout = self.header_layout();
+        header_layout.x += offset.x;
+        header_layout.y += offset.y;
+        if !header_layout.check_collision_point_rec(position) {
+            return None;
+        }
+        let x = position.x - header_layout.x + self.horizontal_bar.value();
+        let columns = self.columns.borrow();
+        for (index, column) in columns.iter().enumerate() {
+            let right = column.x + column.computed_width;
+            if (x - right).abs() <= TABLE_RESIZE_HANDLE_WIDTH {
+                return Some((index, true));
+            }
+            if x >= column.x && x < right {
+                return Some((index, false));
+            }
+        }
+        None
+    }
+
+    fn row_at(&self, offset: &Vector2, position: &Vector2) -> Option<usize> {
+        let mut body_layout = self.body_layout.get();
+        body_layout.x += offset.x;
+        body_layout.y += offset.y;
+        if !body_layout.check_collision_point_rec(position) {
+            return None;
+        }
+        let y = position.y - body_layout.y;
+        let row_height = self.row_height.get();
+        self.visible_rows.borrow().iter()
+            .find(|r| y >= r.y && y < r.y + row_height)
+            .map(|r| r.row)
+    }
+
+    fn handle_header_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        let drag_info = mouse_state.drag_info();
+
+        if drag_info.started() {
+            match self.header_at(offset, drag_info.starting_position()) {
+                Some((column, true)) => {
+                    let width = self.columns.borrow()[column].computed_width;
+                    self.resized_column.set(Some((column, width)));
+                }
+                Some((column, false)) => self.pressed_header.set(Some(column)),
+                None => {}
+            }
+        } else if drag_info.in_progress() {
+            if let Some((column, starting_width)) = self.resized_column.get() {
+                let width = (starting_width + drag_info.delta().x).max(TABLE_MIN_COLUMN_WIDTH);
+                self.set_column_width(gui, column, ColumnWidth::Fixed(width));
+            }
+        } else if drag_info.done() {
+            self.resized_column.set(None);
+            if let Some(pressed) = self.pressed_header.take() {
+                if let Some((column, false)) = self.header_at(offset, drag_info.current_position()) {
+                    if column == pressed {
+                        let order = match self.sort.get() {
+                            Some((sorted, order)) if sorted == column => order.reversed(),
+                            _ => SortOrder::Ascending
+                        };
+                        self.sort_by(gui, column, order);
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_text_in(&self, d: &mut impl RaylibDraw, gui: &Gui, text: &str, text_size: &Size, cell_layout: &Rectangle) {
+        if let Some(ts) = self.text_style() {
+            gui.push_clip(cell_layout);
+            let position = Vector2::new(cell_layout.x + TABLE_CELL_MARGIN, cell_layout.y + (cell_layout.height - text_size.height()) * 0.5);
+            ts.draw_text(d, text, &position);
+            gui.pop_clip();
+        }
+    }
+
+    fn render_sort_indicator(&self, d: &mut impl RaylibDraw, header_cell: &Rectangle, order: SortOrder) {
+        let half = TABLE_SORT_INDICATOR_SIZE * 0.5;
+        let center = Vector2::new(header_cell.x + header_cell.width - TABLE_CELL_MARGIN - half, header_cell.y + header_cell.height * 0.5);
+        let (v1, v2, v3) = match order {
+            SortOrder::Ascending => (
+                Vector2::new(center.x, center.y - half),
+                Vector2::new(center.x - half, center.y + half),
+                Vector2::new(center.x + half, center.y + half),
+            ),
+            SortOrder::Descending => (
+                Vector2::new(center.x - half, center.y - half),
+                Vector2::new(center.x, center.y + half),
+                Vector2::new(center.x + half, center.y - half),
+            ),
+        };
+        d.draw_triangle(v1, v2, v3, TABLE_SORT_INDICATOR_COLOR);
+    }
+}
+
+impl WidgetSpecific for TablePar {
+
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, _gui: &Gui) -> Size {
+        let mut header_height: f32 = 0.0;
+        for column in self.columns.borrow_mut().iter_mut() {
+            column.title_size = self.measure(&column.title);
+            header_height = header_height.max(column.title_size.height());
+        }
+        self.header_height.set((header_height + 2.0 * TABLE_CELL_MARGIN).max(TABLE_DEFAULT_ROW_HEIGHT));
+
+        let default_size = Size::new(TABLE_DEFAULT_WIDTH, TABLE_DEFAULT_HEIGHT).with_padding(&self.padding());
+        let mut preferred = self.preferred_size();
+        preferred.replace_empty_dimensions(&default_size);
+        preferred
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {
+        let content_layout = self.content_layout();
+        let header_height = self.header_height.get();
+        let rows_height = self.row_count.get() as f32 * self.row_height.get();
+        let available_height = (content_layout.height - header_height).max(0.0);
+
+        let mut body_width = content_layout.width;
+        let mut body_height = available_height;
+
+        // the auto columns may grow when the visible rows are built, the layout is then done again
+        for _ in 0..2 {
+            self.layout_columns(content_layout.width);
+            let vertical_needed = rows_height > available_height
+                || (self.columns_width() > content_layout.width && rows_height > available_height - SCROLL_BAR_THICKNESS);
+            body_width = if vertical_needed { (content_layout.width - SCROLL_BAR_THICKNESS).max(0.0) } else { content_layout.width };
+            self.layout_columns(body_width);
+            let horizontal_needed = self.columns_width() > body_width;
+            body_height = if horizontal_needed { (available_height - SCROLL_BAR_THICKNESS).max(0.0) } else { available_height };
+
+            self.body_layout.set(Rectangle::new(content_layout.x, content_layout.y + header_height, body_width, body_height));
+            self.vertical_bar.set_lengths(rows_height, body_height);
+            self.horizontal_bar.set_lengths(self.columns_width(), body_width);
+
+            if !self.update_visible_rows() {
+                break;
+            }
+        }
+
+        self.vertical_bar.set_layout(Rectangle::new(content_layout.x + body_width, content_layout.y + header_height, SCROLL_BAR_THICKNESS, body_height));
+        self.horizontal_bar.set_layout(Rectangle::new(content_layout.x, content_layout.y + header_height + body_height, body_width, SCROLL_BAR_THICKNESS));
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        let mut scrolled = self.vertical_bar.update_action(offset, mouse_state);
+        scrolled |= self.horizontal_bar.update_action(offset, mouse_state);
+
+        let hoovered = self.get_hoover_state();
+        let wheel_move = mouse_state.wheel_move();
+        if hoovered && wheel_move != 0.0 {
+            scrolled |= self.vertical_bar.scroll_by(-wheel_move * TABLE_WHEEL_STEP);
+        }
+        if scrolled {
+            self.invalidate_position(gui);
+        }
+
+        self.handle_header_action(gui, offset, mouse_state);
+
+        let dragging = self.vertical_bar.is_dragging() || self.horizontal_bar.is_dragging() || self.resized_column.get().is_some();
+        let hoovered_row = if hoovered && !dragging {
+            self.row_at(offset, mouse_state.mouse_position())
+        } else {
+            None
+        };
+        self.hoovered_row.set(hoovered_row);
+
+        if mouse_state.left().is_pressed() {
+            if let Some(row) = hoovered_row {
+                if self.selection.borrow_mut().click(row) {
+                    self.fire_selection_event(gui);
+                }
+            }
+        }
+
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn render_my_visual(&self, gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let mut content_layout = self.content_layout();
+        content_layout.x += offset.x;
+        content_layout.y += offset.y;
+        gui.push_clip(&content_layout);
+
+        let scroll_x = self.horizontal_bar.value();
+        let row_height = self.row_height.get();
+        let columns = self.columns.borrow();
+
+        let mut body_layout = self.body_layout.get();
+        body_layout.x += offset.x;
+        body_layout.y += offset.y;
+
+        {
+            gui.push_clip(&body_layout);
+            let selection = self.selection.borrow();
+            let hoovered_row = self.hoovered_row.get();
+            for visible_row in self.visible_rows.borrow().iter() {
+                let row_layout = Rectangle::new(body_layout.x, body_layout.y + visible_row.y, body_layout.width, row_height);
+                if selection.is_selected(visible_row.row) {
+                    d.draw_rectangle_rec(row_layout, TABLE_SELECTED_COLOR);
+                } else if hoovered_row == Some(visible_row.row) {
+                    d.draw_rectangle_rec(row_layout, TABLE_HOOVERED_COLOR);
+                }
+                for (column, cell) in columns.iter().zip(visible_row.cells.iter()) {
+                    let cell_layout = Rectangle::new(body_layout.x + column.x - scroll_x, row_layout.y, column.computed_width, row_height);
+                    self.draw_text_in(d, gui, &cell.text, &cell.text_size, &cell_layout);
+                }
+            }
+            gui.pop_clip();
+        }
+
+        {
+            let mut header_layout = self.header_layout();
+            header_layout.x += offset.x;
+            header_layout.y += offset.y;
+            d.draw_rectangle_rec(header_layout, TABLE_HEADER_COLOR);
+
+            gui.push_clip(&header_layout);
+            let sort = self.sort.get();
+            for (index, column) in columns.iter().enumerate() {
+                let header_cell = Rectangle::new(header_layout.x + column.x - scroll_x, header_layout.y, column.computed_width, header_layout.height);
+                self.draw_text_in(d, gui, &column.title, &column.title_size, &header_cell);
+                if let Some((sorted, order)) = sort {
+                    if sorted == index {
+                        self.render_sort_indicator(d, &header_cell, order);
+                    }
+                }
+                let right = header_cell.x + header_cell.width;
+                d.draw_line_v(Vector2::new(right, header_cell.y), Vector2::new(right, header_cell.y + header_cell.height), TABLE_GRID_COLOR);
+            }
+            d.draw_line_v(Vector2::new(header_layout.x, body_layout.y), Vector2::new(header_layout.x + header_layout.width, body_layout.y), TABLE_GRID_COLOR);
+            gui.pop_clip();
+        }
+
+        self.vertical_bar.render(d, offset);
+        self.horizontal_bar.render(d, offset);
+
+        gui.pop_clip();
+    }
+
+}
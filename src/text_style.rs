@@ -15,6 +15,10 @@ impl TextStyle {
         Self{font:font.clone(), color, spacing}
     }
 
+    pub fn font(&self) -> &Rc<FontInfo> {
+        &self.font
+    }
+
     pub fn color(&self) -> &Color {
         &self.color
     }
@@ -31,5 +35,10 @@ impl TextStyle {
         self.font.draw_text(d,text, position,self.spacing,self.color);
     }
 
+    /// Draw the text with the font of the style but another color
+    pub fn draw_text_with_color(&self, d: &mut impl RaylibDraw, text: &str, position: &Vector2, color: Color) {
+        self.font.draw_text(d,text, position,self.spacing,color);
+    }
+
 
 }
\ No newline at end of file
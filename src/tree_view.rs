@@ -0,0 +1,486 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+
+use raylib::prelude::*;
+
+use crate::event::Event::Select;
+use crate::event::SelectPar;
+use crate::gui::Gui;
+use crate::keyboard::KeyboardState;
+use crate::mouse::MouseState;
+use crate::orientation::Orientation::Vertical;
+use crate::scroll_bar::{ScrollBar, SCROLL_BAR_THICKNESS};
+use crate::size::Size;
+use crate::widget_data::WidgetData;
+use crate::widget_operation::WidgetSpecific;
+
+/// The data displayed by a tree view. The nodes are identified by an id
+/// chosen by the model.
+pub trait TreeModel {
+    /// The ids of the top level nodes
+    fn roots(&self) -> Vec<usize>;
+    /// true if the node can be expanded
+    fn has_children(&self, node_id: usize) -> bool;
+    /// The ids of the children of the node. Only called the first time the node is expanded
+    fn children(&self, node_id: usize) -> Vec<usize>;
+    /// The text displayed for the node
+    fn text(&self, node_id: usize) -> String;
+}
+
+/// A node of the tree that is reachable from the roots through expanded nodes
+struct TreeRow {
+    node_id: usize,
+    parent: Option<usize>,
+    depth: usize,
+    expandable: bool,
+}
+
+/// A row currently displayed by the tree
+struct VisibleRow {
+    /// index of the row in the flattened rows
+    row: usize,
+    text: String,
+    text_size: Size,
+    /// layout of the row, in the same coordinates as the content layout of the tree
+    layout: Rectangle,
+}
+
+/// A tree view backed by a `TreeModel`. Only the visible rows are built and the
+/// children of a node are requested to the model when it is expanded for the first time.
+pub struct TreeViewPar {
+    widget_data: WidgetData,
+    model: RefCell<Option<Box<dyn TreeModel>>>,
+    roots: RefCell<Option<Vec<usize>>>,
+    children_cache: RefCell<HashMap<usize, Vec<usize>>>,
+    expanded: RefCell<HashSet<usize>>,
+    rows: RefCell<Vec<TreeRow>>,
+    rows_valid: Cell<bool>,
+    row_height: Cell<f32>,
+    visible_rows: RefCell<Vec<VisibleRow>>,
+    hoovered_row: Cell<Option<usize>>,
+    selected_node: Cell<Option<usize>>,
+    scroll_bar: ScrollBar,
+}
+
+impl Deref for TreeViewPar {
+    type Target = WidgetData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget_data
+    }
+}
+
+const TREE_DEFAULT_WIDTH: f32 = 200.0;
+const TREE_DEFAULT_HEIGHT: f32 = 200.0;
+const TREE_DEFAULT_ROW_HEIGHT: f32 = 30.0;
+const TREE_INDENT: f32 = 16.0;
+const TREE_TOGGLE_SIZE: f32 = 16.0;
+const TREE_TEXT_MARGIN: f32 = 4.0;
+const TREE_WHEEL_STEP: f32 = 40.0;
+const TREE_TOGGLE_COLOR: Color = Color::DARKGRAY;
+const TREE_SELECTED_COLOR: Color = Color::SKYBLUE;
+const TREE_HOOVERED_COLOR: Color = Color::LIGHTGRAY;
+
+impl TreeViewPar {
+    pub fn new() -> Self {
+        let tree_view_par = Self {
+            widget_data: WidgetData::new(),
+            model: RefCell::new(None),
+            roots: RefCell::new(None),
+            children_cache: RefCell::new(HashMap::new()),
+            expanded: RefCell::new(HashSet::new()),
+            rows: RefCell::new(Vec::new()),
+            rows_valid: Cell::new(false),
+            row_height: Cell::new(TREE_DEFAULT_ROW_HEIGHT),
+            visible_rows: RefCell::new(Vec::new()),
+            hoovered_row: Cell::new(None),
+            selected_node: Cell::new(None),
+            scroll_bar: ScrollBar::new(Vertical),
+        };
+        tree_view_par.set_hooverable(true);
+        tree_view_par.set_focusable(true);
+        tree_view_par
+    }
+
+    pub fn set_model(&self, gui: &Gui, model: impl TreeModel + 'static) -> &TreeViewPar {
+        self.model.replace(Some(Box::new(model)));
+        self.expanded.borrow_mut().clear();
+        self.selected_node.set(None);
+        self.refresh(gui)
+    }
+
+    /// Drop the nodes obtained from the model so far. They are requested again
+    /// for the expanded nodes.
+    pub fn refresh(&self, gui: &Gui) -> &TreeViewPar {
+        self.roots.replace(None);
+        self.children_cache.borrow_mut().clear();
+        self.invalidate_rows(gui);
+        self
+    }
+
+    /// Drop the children of the node obtained from the model so far
+    pub fn refresh_node(&self, gui: &Gui, node_id: usize) -> &TreeViewPar {
+        self.children_cache.borrow_mut().remove(&node_id);
+        self.invalidate_rows(gui);
+        self
+    }
+
+    pub fn set_row_height(&self, gui: &Gui, row_height: f32) -> &TreeViewPar {
+        self.row_height.set(row_height.max(1.0));
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn is_expanded(&self, node_id: usize) -> bool {
+        self.expanded.borrow().contains(&node_id)
+    }
+
+    pub fn expand(&self, gui: &Gui, node_id: usize) -> &TreeViewPar {
+        if self.expanded.borrow_mut().insert(node_id) {
+            self.invalidate_rows(gui);
+        }
+        self
+    }
+
+    /// Collapse the node. If the selected node is hidden by the collapse, the collapsed node is selected
+    pub fn collapse(&self, gui: &Gui, node_id: usize) -> &TreeViewPar {
+        if !self.expanded.borrow_mut().remove(&node_id) {
+            return self;
+        }
+        self.invalidate_rows(gui);
+        if let Some(selected) = self.selected_node.get() {
+            self.ensure_rows();
+            if self.row_of(selected).is_none() {
+                self.select_node(gui, node_id);
+            }
+        }
+        self
+    }
+
+    pub fn toggle(&self, gui: &Gui, node_id: usize) -> &TreeViewPar {
+        if self.is_expanded(node_id) {
+            self.collapse(gui, node_id)
+        } else {
+            self.expand(gui, node_id)
+        }
+    }
+
+    pub fn selected_node(&self) -> Option<usize> {
+        self.selected_node.get()
+    }
+
+    pub fn select_node(&self, gui: &Gui, node_id: usize) -> &TreeViewPar {
+        self.set_selected_node(gui, Some(node_id));
+        self
+    }
+
+    pub fn clear_selection(&self, gui: &Gui) -> &TreeViewPar {
+        self.set_selected_node(gui, None);
+        self
+    }
+
+    fn set_selected_node(&self, gui: &Gui, selected_node: Option<usize>) {
+        if self.selected_node.get() == selected_node {
+            return;
+        }
+        self.selected_node.set(selected_node);
+        if let Some(action_id) = self.action_id() {
+            gui.add_event(Select(SelectPar::new(&action_id, selected_node.into_iter().collect())))
+        }
+    }
+
+    fn invalidate_rows(&self, gui: &Gui) {
+        self.rows_valid.set(false);
+        self.invalidate_position(gui);
+    }
+
+    /// Flatten the tree : the expanded nodes are followed, loading their children if needed
+    fn ensure_rows(&self) {
+        if self.rows_valid.get() {
+            return;
+        }
+        let mut rows = Vec::new();
+        if let Some(model) = self.model.borrow().as_ref() {
+            let roots = self.roots.borrow_mut().get_or_insert_with(|| model.roots()).clone();
+            let expanded = self.expanded.borrow();
+            let mut children_cache = self.children_cache.borrow_mut();
+
+            let mut stack: Vec<(usize, Option<usize>, usize)> = roots.iter().rev().map(|node_id| (*node_id, None, 0)).collect();
+            while let Some((node_id, parent, depth)) = stack.pop() {
+                let expandable = model.has_children(node_id);
+                rows.push(TreeRow { node_id, parent, depth, expandable });
+                if expandable && expanded.contains(&node_id) {
+                    let children = children_cache.entry(node_id).or_insert_with(|| model.children(node_id));
+                    stack.extend(children.iter().rev().map(|child| (*child, Some(node_id), depth + 1)));
+                }
+            }
+        }
+        self.rows.replace(rows);
+        self.rows_valid.set(true);
+        // the visible rows refer to the previous rows until the next layout
+        self.visible_rows.borrow_mut().clear();
+    }
+
+    fn row_of(&self, node_id: usize) -> Option<usize> {
+        self.rows.borrow().iter().position(|r| r.node_id == node_id)
+    }
+
+    fn update_visible_rows(&self, rows_width: f32) {
+        let content_layout = self.content_layout();
+        let row_height = self.row_height.get();
+        let scroll = self.scroll_bar.value();
+        let rows = self.rows.borrow();
+
+        let mut visible_rows = Vec::new();
+        if let Some(model) = self.model.borrow().as_ref() {
+            let mut row = (scroll / row_height) as usize;
+            while row < rows.len() {
+                let top = row as f32 * row_height - scroll;
+                if top >= content_layout.height {
+                    break;
+                }
+                let text = model.text(rows[row].node_id);
+                let text_size = match self.text_style() {
+                    None => Size::empty(),
+                    Some(ts) => ts.measure_text(&text)
+                };
+                let layout = Rectangle::new(content_layout.x, content_layout.y + top, rows_width, row_height);
+                visible_rows.push(VisibleRow { row, text, text_size, layout });
+                row += 1;
+            }
+        }
+        self.visible_rows.replace(visible_rows);
+    }
+
+    fn toggle_layout(&self, row_layout: &Rectangle, depth: usize) -> Rectangle {
+        Rectangle::new(
+            row_layout.x + depth as f32 * TREE_INDENT,
+            row_layout.y + (row_layout.height - TREE_TOGGLE_SIZE) * 0.5,
+            TREE_TOGGLE_SIZE,
+            TREE_TOGGLE_SIZE,
+        )
+    }
+
+    /// the row under the mouse and true if the mouse is on its expand/collapse toggle
+    fn visible_row_at(&self, offset: &Vector2, mouse_position: &Vector2) -> Option<(usize, bool)> {
+        let mut content_layout = self.content_layout();
+        content_layout.x += offset.x;
+        content_layout.y += offset.y;
+        if !content_layout.check_collision_point_rec(mouse_position) {
+            return None;
+        }
+        let rows = self.rows.borrow();
+        self.visible_rows.borrow().iter().find_map(|visible_row| {
+            let mut layout = visible_row.layout;
+            layout.x += offset.x;
+            layout.y += offset.y;
+            if !layout.check_collision_point_rec(mouse_position) {
+                return None;
+            }
+            let tree_row = &rows[visible_row.row];
+            let on_toggle = tree_row.expandable && self.toggle_layout(&layout, tree_row.depth).check_collision_point_rec(mouse_position);
+            Some((visible_row.row, on_toggle))
+        })
+    }
+
+    fn select_row(&self, gui: &Gui, row: usize) {
+        let node_id = match self.rows.borrow().get(row) {
+            None => return,
+            Some(tree_row) => tree_row.node_id
+        };
+        self.set_selected_node(gui, Some(node_id));
+        let row_height = self.row_height.get();
+        let top = row as f32 * row_height;
+        if self.scroll_bar.scroll_to_show(top, top + row_height) {
+            self.invalidate_position(gui);
+        }
+    }
+
+    fn render_toggle(&self, d: &mut impl RaylibDraw, toggle_layout: &Rectangle, expanded: bool) {
+        let half = TREE_TOGGLE_SIZE * 0.25;
+        let center = Vector2::new(toggle_layout.x + toggle_layout.width * 0.5, toggle_layout.y + toggle_layout.height * 0.5);
+        if expanded {
+            d.draw_triangle(
+                Vector2::new(center.x - half, center.y - half),
+                Vector2::new(center.x, center.y + half),
+                Vector2::new(center.x + half, center.y - half),
+                TREE_TOGGLE_COLOR,
+            );
+        } else {
+            d.draw_triangle(
+                Vector2::new(center.x - half, center.y - half),
+                Vector2::new(center.x - half, center.y + half),
+                Vector2::new(center.x + half, center.y),
+                TREE_TOGGLE_COLOR,
+            );
+        }
+    }
+}
+
+impl WidgetSpecific for TreeViewPar {
+
+    fn widget_data(&self) -> &WidgetData {
+        &self.widget_data
+    }
+
+    fn widget_data_mut(&mut self) -> &mut WidgetData {
+        &mut self.widget_data
+    }
+
+    fn compute_size(&self, _gui: &Gui) -> Size {
+        let default_size = Size::new(TREE_DEFAULT_WIDTH, TREE_DEFAULT_HEIGHT).with_padding(&self.padding());
+        let mut preferred = self.preferred_size();
+        preferred.replace_empty_dimensions(&default_size);
+        preferred
+    }
+
+    fn compute_child_content_size(&self, _gui: &Gui, _available_size: Size) {}
+
+    fn compute_child_positions(&self, _gui: &Gui) {
+        self.ensure_rows();
+
+        let content_layout = self.content_layout();
+        let total_height = self.rows.borrow().len() as f32 * self.row_height.get();
+        self.scroll_bar.set_lengths(total_height, content_layout.height);
+
+        let rows_width = if self.scroll_bar.is_needed() {
+            let rows_width = (content_layout.width - SCROLL_BAR_THICKNESS).max(0.0);
+            self.scroll_bar.set_layout(Rectangle::new(content_layout.x + rows_width, content_layout.y, SCROLL_BAR_THICKNESS, content_layout.height));
+            rows_width
+        } else {
+            content_layout.width
+        };
+
+        self.update_visible_rows(rows_width);
+    }
+
+    fn update_action(&self, gui: &Gui, offset: &Vector2, mouse_state: &MouseState) {
+        let mut scrolled = self.scroll_bar.update_action(offset, mouse_state);
+
+        let hoovered = self.get_hoover_state();
+        let wheel_move = mouse_state.wheel_move();
+        if hoovered && wheel_move != 0.0 {
+            scrolled |= self.scroll_bar.scroll_by(-wheel_move * TREE_WHEEL_STEP);
+        }
+        if scrolled {
+            self.invalidate_position(gui);
+        }
+
+        let row_under_mouse = if hoovered && !self.scroll_bar.is_dragging() {
+            self.visible_row_at(offset, mouse_state.mouse_position())
+        } else {
+            None
+        };
+        self.hoovered_row.set(row_under_mouse.map(|(row, _)| row));
+
+        if mouse_state.left().is_pressed() {
+            match row_under_mouse {
+                Some((row, true)) => {
+                    let node_id = self.rows.borrow()[row].node_id;
+                    self.toggle(gui, node_id);
+                }
+                Some((row, false)) => self.select_row(gui, row),
+                None => {}
+            }
+        }
+
+        self.widget_data.wd_update_action(gui, offset, mouse_state);
+    }
+
+    fn update_key_action(&self, gui: &Gui, keyboard_state: &KeyboardState) {
+        for key in keyboard_state.pressed_keys() {
+            self.ensure_rows();
+            let nb_rows = self.rows.borrow().len();
+            if nb_rows == 0 {
+                return;
+            }
+
+            let row = match self.selected_node.get().and_then(|node_id| self.row_of(node_id)) {
+                None => {
+                    self.select_row(gui, 0);
+                    continue;
+                }
+                Some(row) => row
+            };
+            let (node_id, expandable, parent, first_child) = {
+                let rows = self.rows.borrow();
+                let first_child = rows.get(row + 1).filter(|r| r.parent == Some(rows[row].node_id)).map(|_| row + 1);
+                (rows[row].node_id, rows[row].expandable, rows[row].parent, first_child)
+            };
+            let page = ((self.content_layout().height / self.row_height.get()) as usize).max(1);
+
+            match key {
+                KeyboardKey::KEY_DOWN => self.select_row(gui, (row + 1).min(nb_rows - 1)),
+                KeyboardKey::KEY_UP => self.select_row(gui, row.saturating_sub(1)),
+                KeyboardKey::KEY_PAGE_DOWN => self.select_row(gui, (row + page).min(nb_rows - 1)),
+                KeyboardKey::KEY_PAGE_UP => self.select_row(gui, row.saturating_sub(page)),
+                KeyboardKey::KEY_HOME => self.select_row(gui, 0),
+                KeyboardKey::KEY_END => self.select_row(gui, nb_rows - 1),
+                KeyboardKey::KEY_RIGHT if expandable => {
+                    match (self.is_expanded(node_id), first_child) {
+                        (false, _) => { self.expand(gui, node_id); }
+                        (true, Some(first_child)) => self.select_row(gui, first_child),
+                        (true, None) => {}
+                    }
+                }
+                KeyboardKey::KEY_LEFT => {
+                    if expandable && self.is_expanded(node_id) {
+                        self.collapse(gui, node_id);
+                    } else if let Some(parent) = parent {
+                        self.select_node(gui, parent);
+                    }
+                }
+                KeyboardKey::KEY_ENTER | KeyboardKey::KEY_KP_ENTER | KeyboardKey::KEY_SPACE if expandable => {
+                    self.toggle(gui, node_id);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn render_my_visual(&self, gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
+        let mut content_layout = self.content_layout();
+        content_layout.x += offset.x;
+        content_layout.y += offset.y;
+
+        gui.push_clip(&content_layout);
+
+        let rows = self.rows.borrow();
+        let selected_node = self.selected_node.get();
+        let hoovered_row = self.hoovered_row.get();
+        let text_style = self.text_style();
+        let expanded = self.expanded.borrow();
+
+        for visible_row in self.visible_rows.borrow().iter() {
+            let tree_row = &rows[visible_row.row];
+            let mut layout = visible_row.layout;
+            layout.x += offset.x;
+            layout.y += offset.y;
+
+            if selected_node == Some(tree_row.node_id) {
+                d.draw_rectangle_rec(layout, TREE_SELECTED_COLOR);
+            } else if hoovered_row == Some(visible_row.row) {
+                d.draw_rectangle_rec(layout, TREE_HOOVERED_COLOR);
+            }
+
+            let toggle_layout = self.toggle_layout(&layout, tree_row.depth);
+            if tree_row.expandable {
+                self.render_toggle(d, &toggle_layout, expanded.contains(&tree_row.node_id));
+            }
+
+            if let Some(ts) = text_style.as_ref() {
+                let position = Vector2::new(
+                    toggle_layout.x + toggle_layout.width + TREE_TEXT_MARGIN,
+                    layout.y + (layout.height - visible_row.text_size.height()) * 0.5,
+                );
+                ts.draw_text(d, &visible_row.text, &position);
+            }
+        }
+
+        self.scroll_bar.render(d, offset);
+
+        gui.pop_clip();
+    }
+
+}
@@ -3,14 +3,20 @@ use crate::widget_operation::{LayoutableWidget, WidgetSpecific};
 use crate::gui::{Gui};
 use crate::size::{Size};
 use std::cell::Cell;
-use crate::fill::Fill;
+use crate::fill::{share_by_weight, Fill};
 use raylib::prelude::*;
 use std::ops::Deref;
 use crate::mouse::MouseState;
+use crate::widget::Widget;
+use crate::alignment::Justify;
 
+/// A container placing its children from top to bottom. Each child is aligned horizontally
+/// through its own alignment, and takes the width of the box when it fills the width or is
+/// aligned with `HAlignment::Stretch`
 pub struct VBoxPar {
     widget_data: WidgetData,
     spacing: Cell<f32>,
+    justify: Cell<Justify>,
 }
 
 impl Deref for VBoxPar {
@@ -23,7 +29,7 @@ impl Deref for VBoxPar {
 
 impl VBoxPar {
     pub fn new() -> Self {
-        Self { widget_data: WidgetData::new(), spacing: Cell::new(10.0) }
+        Self { widget_data: WidgetData::new(), spacing: Cell::new(10.0), justify: Cell::new(Justify::Start) }
     }
 
     pub fn set_spacing(&self, gui: &Gui, spacing: f32) -> &VBoxPar {
@@ -38,6 +44,29 @@ impl VBoxPar {
     pub fn get_spacing(&self) -> f32 {
         self.spacing.get()
     }
+
+    /// How the children are spread along the height of the box when they do not fill it
+    pub fn set_justify(&self, gui: &Gui, justify: Justify) -> &VBoxPar {
+        if justify.eq(&self.justify.get()) {
+            return self;
+        }
+        self.justify.set(justify);
+        self.invalidate_position(gui);
+        self
+    }
+
+    pub fn get_justify(&self) -> Justify {
+        self.justify.get()
+    }
+
+    /// The width a child gets from the box, which decides its height when it depends on its width
+    fn child_width(gui: &Gui, child: &Widget, width: f32) -> f32 {
+        if child.stretch_width() {
+            width
+        } else {
+            child.compute_computed_size(gui).width().min(width)
+        }
+    }
 }
 
 // impl WidgetDataProvider for VBoxPar {
@@ -99,8 +128,12 @@ impl WidgetSpecific for VBoxPar {
         }
         let tree_index = tree_index.unwrap();
 
+        let padding = self.padding();
+        let width = available_size.width() - padding.h_padding();
+        let height= available_size.height() - padding.v_padding();
+
         let mut summed_fixed_height:f32 = 0.0;
-        let mut summed_weight:u32 = 0;
+        let mut shares = Vec::new();
         let mut nb_children = 0;
 
         for child_index in gui.get_widget_children(tree_index) {
@@ -109,23 +142,19 @@ impl WidgetSpecific for VBoxPar {
                 nb_children += 1;
                 match fill {
                     Fill::Disabled => {
-                        summed_fixed_height += child.compute_computed_size(gui).height();
+                        summed_fixed_height += child.compute_height_for_width(gui, Self::child_width(gui, child, width));
                     }
                     Fill::Enabled { weight} => {
-                        summed_weight += weight;
+                        shares.push((weight, child.min_size().height(), child.max_size().height()));
                     }
                 }
             }
         }
 
-        let padding = self.padding();
-        let width = available_size.width() - padding.h_padding();
-        let height= available_size.height() - padding.v_padding();
-
         let space_taken_by_spacing = self.spacing.get() * ((nb_children - 1).max(0) as f32);
 
 
-        let fill_height = (height-space_taken_by_spacing-summed_fixed_height)/(summed_weight.max(1) as f32);
+        let mut fill_heights = share_by_weight(height-space_taken_by_spacing-summed_fixed_height, &shares).into_iter();
 
         if width<0.0 || height<=0.0 {
             return
@@ -137,12 +166,12 @@ impl WidgetSpecific for VBoxPar {
                 let fill = child.fill_height();
                 match fill {
                     Fill::Disabled => {
-                        let child_height = child.compute_computed_size(gui).height();
+                        let child_height = child.compute_height_for_width(gui, Self::child_width(gui, child, width));
                         size.set_height(child_height);
                         child.update_content_size(gui,&size);
                     }
-                    Fill::Enabled { weight } => {
-                        size.set_height(fill_height * weight as f32);
+                    Fill::Enabled { .. } => {
+                        size.set_height(fill_heights.next().unwrap_or(0.0));
                         child.update_content_size(gui, &size)
                     }
                 }
@@ -164,18 +193,38 @@ impl WidgetSpecific for VBoxPar {
         };
 
         let spacing = self.spacing.get();
+        let children: Vec<&Widget> = gui.get_widget_children(tree_index).filter_map(|child_index| gui.get_widget(child_index)).collect();
+
+        let used_height = children.iter().map(|w| w.widget_height()).sum::<f32>() + spacing * ((children.len() as i32 - 1).max(0) as f32);
+        let (start, extra_spacing) = self.justify.get().distribute(content_size.height() - used_height, children.len());
+
+        let mut position = Vector2::new(0.0,start);
+        for w in children {
+            {
+                position.x = -(content_size.width() - w.widget_width()) * w.alignment().horizontal.shift_factor();
+                w.set_widget_target(&position);
+                w.update_child_positions(gui);
+            }
+            position.y += w.widget_height() + spacing + extra_spacing;
+        }
+    }
 
-        let mut position = Vector2::new(0.0,0.0);
+    fn height_for_width(&self, gui: &Gui, width: f32) -> Option<f32> {
+        let tree_index = self.get_tree_index()?;
+        let padding = self.padding();
+        let content_width = width - padding.h_padding();
+
+        let mut nb_children = 0;
+        let mut summed_height: f32 = 0.0;
         for child_index in gui.get_widget_children(tree_index) {
-            if let Some(w) = gui.get_widget(child_index) {
-                {
-                    position.x = (content_size.width() - w.widget_width())*0.5;
-                    w.set_widget_target(&position);
-                    w.update_child_positions(gui);
-                }
-                position.y += w.widget_height() + spacing;
+            if let Some(child) = gui.get_widget(child_index) {
+                nb_children += 1;
+                summed_height += child.compute_height_for_width(gui, Self::child_width(gui, child, content_width));
             }
         }
+        summed_height += self.spacing.get() * ((nb_children - 1).max(0) as f32);
+
+        Some(self.preferred_size().height().max(summed_height + padding.v_padding()))
     }
 
     fn render_my_visual(&self, _gui: &Gui, _d: &mut impl RaylibDraw, _offset: &Vector2) {
@@ -14,13 +14,48 @@ use crate::slider::SliderPar;
 use raylib::prelude::*;
 use std::ops::{Deref, DerefMut};
 use crate::mouse::MouseState;
+use crate::keyboard::KeyboardState;
+use crate::list_view::ListViewPar;
+use crate::table::TablePar;
+use crate::tree_view::TreeViewPar;
+use crate::tab_pane::TabPanePar;
+use crate::scroll_pane::ScrollPanePar;
+use crate::progress_bar::ProgressBarPar;
+use crate::image::ImagePar;
+use crate::menu_bar::MenuBarPar;
+use crate::split_pane::SplitPanePar;
+use crate::color_picker::ColorPickerPar;
+use crate::range_slider::RangeSliderPar;
+use crate::canvas::CanvasPar;
+use crate::chart::ChartPar;
+use crate::rich_text::RichTextPar;
+use crate::grid::GridPar;
+use crate::flow::FlowPar;
+use crate::stack::StackPar;
 
 pub enum  Widget {
     Label(LabelPar),
     Pane(PanePar),
     VBox(VBoxPar),
     HBox(HBoxPar),
-    Slider(SliderPar)
+    Slider(SliderPar),
+    ListView(ListViewPar),
+    Table(TablePar),
+    TreeView(TreeViewPar),
+    TabPane(TabPanePar),
+    ScrollPane(ScrollPanePar),
+    ProgressBar(ProgressBarPar),
+    Image(ImagePar),
+    MenuBar(MenuBarPar),
+    SplitPane(SplitPanePar),
+    ColorPicker(ColorPickerPar),
+    RangeSlider(RangeSliderPar),
+    Canvas(CanvasPar),
+    Chart(ChartPar),
+    RichText(RichTextPar),
+    Grid(GridPar),
+    Flow(FlowPar),
+    Stack(StackPar),
 }
 
 
@@ -34,6 +69,23 @@ impl Deref for Widget {
             Widget::VBox(p) => p.widget_data(),
             Widget::HBox(p) => p.widget_data(),
             Widget::Slider(p) => p.widget_data(),
+            Widget::ListView(p) => p.widget_data(),
+            Widget::Table(p) => p.widget_data(),
+            Widget::TreeView(p) => p.widget_data(),
+            Widget::TabPane(p) => p.widget_data(),
+            Widget::ScrollPane(p) => p.widget_data(),
+            Widget::ProgressBar(p) => p.widget_data(),
+            Widget::Image(p) => p.widget_data(),
+            Widget::MenuBar(p) => p.widget_data(),
+            Widget::SplitPane(p) => p.widget_data(),
+            Widget::ColorPicker(p) => p.widget_data(),
+            Widget::RangeSlider(p) => p.widget_data(),
+            Widget::Canvas(p) => p.widget_data(),
+            Widget::Chart(p) => p.widget_data(),
+            Widget::RichText(p) => p.widget_data(),
+            Widget::Grid(p) => p.widget_data(),
+            Widget::Flow(p) => p.widget_data(),
+            Widget::Stack(p) => p.widget_data(),
         }
     }
 }
@@ -46,6 +98,23 @@ impl DerefMut for Widget {
             Widget::VBox(p) => p.widget_data_mut(),
             Widget::HBox(p) => p.widget_data_mut(),
             Widget::Slider(p) => p.widget_data_mut(),
+            Widget::ListView(p) => p.widget_data_mut(),
+            Widget::Table(p) => p.widget_data_mut(),
+            Widget::TreeView(p) => p.widget_data_mut(),
+            Widget::TabPane(p) => p.widget_data_mut(),
+            Widget::ScrollPane(p) => p.widget_data_mut(),
+            Widget::ProgressBar(p) => p.widget_data_mut(),
+            Widget::Image(p) => p.widget_data_mut(),
+            Widget::MenuBar(p) => p.widget_data_mut(),
+            Widget::SplitPane(p) => p.widget_data_mut(),
+            Widget::ColorPicker(p) => p.widget_data_mut(),
+            Widget::RangeSlider(p) => p.widget_data_mut(),
+            Widget::Canvas(p) => p.widget_data_mut(),
+            Widget::Chart(p) => p.widget_data_mut(),
+            Widget::RichText(p) => p.widget_data_mut(),
+            Widget::Grid(p) => p.widget_data_mut(),
+            Widget::Flow(p) => p.widget_data_mut(),
+            Widget::Stack(p) => p.widget_data_mut(),
         }
     }
 }
@@ -58,6 +127,23 @@ impl LayoutableWidget for Widget {
             Widget::VBox(p) => p.compute_computed_size(gui),
             Widget::HBox(p) => p.compute_computed_size(gui),
             Widget::Slider(p) => p.compute_computed_size(gui),
+            Widget::ListView(p) => p.compute_computed_size(gui),
+            Widget::Table(p) => p.compute_computed_size(gui),
+            Widget::TreeView(p) => p.compute_computed_size(gui),
+            Widget::TabPane(p) => p.compute_computed_size(gui),
+            Widget::ScrollPane(p) => p.compute_computed_size(gui),
+            Widget::ProgressBar(p) => p.compute_computed_size(gui),
+            Widget::Image(p) => p.compute_computed_size(gui),
+            Widget::MenuBar(p) => p.compute_computed_size(gui),
+            Widget::SplitPane(p) => p.compute_computed_size(gui),
+            Widget::ColorPicker(p) => p.compute_computed_size(gui),
+            Widget::RangeSlider(p) => p.compute_computed_size(gui),
+            Widget::Canvas(p) => p.compute_computed_size(gui),
+            Widget::Chart(p) => p.compute_computed_size(gui),
+            Widget::RichText(p) => p.compute_computed_size(gui),
+            Widget::Grid(p) => p.compute_computed_size(gui),
+            Widget::Flow(p) => p.compute_computed_size(gui),
+            Widget::Stack(p) => p.compute_computed_size(gui),
         }
     }
 
@@ -68,6 +154,50 @@ impl LayoutableWidget for Widget {
             Widget::VBox(p) => p.update_content_size(gui, available_space),
             Widget::HBox(p) => p.update_content_size(gui, available_space),
             Widget::Slider(p) => p.update_content_size(gui, available_space),
+            Widget::ListView(p) => p.update_content_size(gui, available_space),
+            Widget::Table(p) => p.update_content_size(gui, available_space),
+            Widget::TreeView(p) => p.update_content_size(gui, available_space),
+            Widget::TabPane(p) => p.update_content_size(gui, available_space),
+            Widget::ScrollPane(p) => p.update_content_size(gui, available_space),
+            Widget::ProgressBar(p) => p.update_content_size(gui, available_space),
+            Widget::Image(p) => p.update_content_size(gui, available_space),
+            Widget::MenuBar(p) => p.update_content_size(gui, available_space),
+            Widget::SplitPane(p) => p.update_content_size(gui, available_space),
+            Widget::ColorPicker(p) => p.update_content_size(gui, available_space),
+            Widget::RangeSlider(p) => p.update_content_size(gui, available_space),
+            Widget::Canvas(p) => p.update_content_size(gui, available_space),
+            Widget::Chart(p) => p.update_content_size(gui, available_space),
+            Widget::RichText(p) => p.update_content_size(gui, available_space),
+            Widget::Grid(p) => p.update_content_size(gui, available_space),
+            Widget::Flow(p) => p.update_content_size(gui, available_space),
+            Widget::Stack(p) => p.update_content_size(gui, available_space),
+        }
+    }
+
+    fn compute_height_for_width(&self, gui: &Gui, width: f32) -> f32 {
+        match self {
+            Widget::Label(p) => p.compute_height_for_width(gui, width),
+            Widget::Pane(p) => p.compute_height_for_width(gui, width),
+            Widget::VBox(p) => p.compute_height_for_width(gui, width),
+            Widget::HBox(p) => p.compute_height_for_width(gui, width),
+            Widget::Slider(p) => p.compute_height_for_width(gui, width),
+            Widget::ListView(p) => p.compute_height_for_width(gui, width),
+            Widget::Table(p) => p.compute_height_for_width(gui, width),
+            Widget::TreeView(p) => p.compute_height_for_width(gui, width),
+            Widget::TabPane(p) => p.compute_height_for_width(gui, width),
+            Widget::ScrollPane(p) => p.compute_height_for_width(gui, width),
+            Widget::ProgressBar(p) => p.compute_height_for_width(gui, width),
+            Widget::Image(p) => p.compute_height_for_width(gui, width),
+            Widget::MenuBar(p) => p.compute_height_for_width(gui, width),
+            Widget::SplitPane(p) => p.compute_height_for_width(gui, width),
+            Widget::ColorPicker(p) => p.compute_height_for_width(gui, width),
+            Widget::RangeSlider(p) => p.compute_height_for_width(gui, width),
+            Widget::Canvas(p) => p.compute_height_for_width(gui, width),
+            Widget::Chart(p) => p.compute_height_for_width(gui, width),
+            Widget::RichText(p) => p.compute_height_for_width(gui, width),
+            Widget::Grid(p) => p.compute_height_for_width(gui, width),
+            Widget::Flow(p) => p.compute_height_for_width(gui, width),
+            Widget::Stack(p) => p.compute_height_for_width(gui, width),
         }
     }
 
@@ -78,6 +208,23 @@ impl LayoutableWidget for Widget {
             Widget::VBox(p) => p.update_child_positions(gui),
             Widget::HBox(p) => p.update_child_positions(gui),
             Widget::Slider(p) => p.update_child_positions(gui),
+            Widget::ListView(p) => p.update_child_positions(gui),
+            Widget::Table(p) => p.update_child_positions(gui),
+            Widget::TreeView(p) => p.update_child_positions(gui),
+            Widget::TabPane(p) => p.update_child_positions(gui),
+            Widget::ScrollPane(p) => p.update_child_positions(gui),
+            Widget::ProgressBar(p) => p.update_child_positions(gui),
+            Widget::Image(p) => p.update_child_positions(gui),
+            Widget::MenuBar(p) => p.update_child_positions(gui),
+            Widget::SplitPane(p) => p.update_child_positions(gui),
+            Widget::ColorPicker(p) => p.update_child_positions(gui),
+            Widget::RangeSlider(p) => p.update_child_positions(gui),
+            Widget::Canvas(p) => p.update_child_positions(gui),
+            Widget::Chart(p) => p.update_child_positions(gui),
+            Widget::RichText(p) => p.update_child_positions(gui),
+            Widget::Grid(p) => p.update_child_positions(gui),
+            Widget::Flow(p) => p.update_child_positions(gui),
+            Widget::Stack(p) => p.update_child_positions(gui),
         }
     }
 
@@ -92,6 +239,79 @@ impl Widget {
             Widget::VBox(p) => p.update_action(gui, offset,mouse_state),
             Widget::HBox(p) => p.update_action(gui, offset,mouse_state),
             Widget::Slider(p) => p.update_action(gui, offset,mouse_state),
+            Widget::ListView(p) => p.update_action(gui, offset,mouse_state),
+            Widget::Table(p) => p.update_action(gui, offset,mouse_state),
+            Widget::TreeView(p) => p.update_action(gui, offset,mouse_state),
+            Widget::TabPane(p) => p.update_action(gui, offset,mouse_state),
+            Widget::ScrollPane(p) => p.update_action(gui, offset,mouse_state),
+            Widget::ProgressBar(p) => p.update_action(gui, offset,mouse_state),
+            Widget::Image(p) => p.update_action(gui, offset,mouse_state),
+            Widget::MenuBar(p) => p.update_action(gui, offset,mouse_state),
+            Widget::SplitPane(p) => p.update_action(gui, offset,mouse_state),
+            Widget::ColorPicker(p) => p.update_action(gui, offset,mouse_state),
+            Widget::RangeSlider(p) => p.update_action(gui, offset,mouse_state),
+            Widget::Canvas(p) => p.update_action(gui, offset,mouse_state),
+            Widget::Chart(p) => p.update_action(gui, offset,mouse_state),
+            Widget::RichText(p) => p.update_action(gui, offset,mouse_state),
+            Widget::Grid(p) => p.update_action(gui, offset,mouse_state),
+            Widget::Flow(p) => p.update_action(gui, offset,mouse_state),
+            Widget::Stack(p) => p.update_action(gui, offset,mouse_state),
+        }
+    }
+
+    pub fn update_key_action(&self, gui:&Gui, keyboard_state: &KeyboardState) {
+        match self {
+            Widget::Label(p) => p.update_key_action(gui, keyboard_state),
+            Widget::Pane(p) => p.update_key_action(gui, keyboard_state),
+            Widget::VBox(p) => p.update_key_action(gui, keyboard_state),
+            Widget::HBox(p) => p.update_key_action(gui, keyboard_state),
+            Widget::Slider(p) => p.update_key_action(gui, keyboard_state),
+            Widget::ListView(p) => p.update_key_action(gui, keyboard_state),
+            Widget::Table(p) => p.update_key_action(gui, keyboard_state),
+            Widget::TreeView(p) => p.update_key_action(gui, keyboard_state),
+            Widget::TabPane(p) => p.update_key_action(gui, keyboard_state),
+            Widget::ScrollPane(p) => p.update_key_action(gui, keyboard_state),
+            Widget::ProgressBar(p) => p.update_key_action(gui, keyboard_state),
+            Widget::Image(p) => p.update_key_action(gui, keyboard_state),
+            Widget::MenuBar(p) => p.update_key_action(gui, keyboard_state),
+            Widget::SplitPane(p) => p.update_key_action(gui, keyboard_state),
+            Widget::ColorPicker(p) => p.update_key_action(gui, keyboard_state),
+            Widget::RangeSlider(p) => p.update_key_action(gui, keyboard_state),
+            Widget::Canvas(p) => p.update_key_action(gui, keyboard_state),
+            Widget::Chart(p) => p.update_key_action(gui, keyboard_state),
+            Widget::RichText(p) => p.update_key_action(gui, keyboard_state),
+            Widget::Grid(p) => p.update_key_action(gui, keyboard_state),
+            Widget::Flow(p) => p.update_key_action(gui, keyboard_state),
+            Widget::Stack(p) => p.update_key_action(gui, keyboard_state),
+        }
+    }
+
+    /// the distance from the top of the widget to the baseline of its first line of text
+    /// when the widget has the given height
+    pub fn baseline(&self, gui:&Gui, height:f32) -> Option<f32> {
+        match self {
+            Widget::Label(p) => p.baseline(gui, height),
+            Widget::Pane(p) => p.baseline(gui, height),
+            Widget::VBox(p) => p.baseline(gui, height),
+            Widget::HBox(p) => p.baseline(gui, height),
+            Widget::Slider(p) => p.baseline(gui, height),
+            Widget::ListView(p) => p.baseline(gui, height),
+            Widget::Table(p) => p.baseline(gui, height),
+            Widget::TreeView(p) => p.baseline(gui, height),
+            Widget::TabPane(p) => p.baseline(gui, height),
+            Widget::ScrollPane(p) => p.baseline(gui, height),
+            Widget::ProgressBar(p) => p.baseline(gui, height),
+            Widget::Image(p) => p.baseline(gui, height),
+            Widget::MenuBar(p) => p.baseline(gui, height),
+            Widget::SplitPane(p) => p.baseline(gui, height),
+            Widget::ColorPicker(p) => p.baseline(gui, height),
+            Widget::RangeSlider(p) => p.baseline(gui, height),
+            Widget::Canvas(p) => p.baseline(gui, height),
+            Widget::Chart(p) => p.baseline(gui, height),
+            Widget::RichText(p) => p.baseline(gui, height),
+            Widget::Grid(p) => p.baseline(gui, height),
+            Widget::Flow(p) => p.baseline(gui, height),
+            Widget::Stack(p) => p.baseline(gui, height),
         }
     }
 
@@ -106,6 +326,23 @@ impl Widget {
             Widget::VBox(p) => p.render(gui, d, offset),
             Widget::HBox(p) => p.render(gui, d, offset),
             Widget::Slider(p) => p.render(gui, d, offset),
+            Widget::ListView(p) => p.render(gui, d, offset),
+            Widget::Table(p) => p.render(gui, d, offset),
+            Widget::TreeView(p) => p.render(gui, d, offset),
+            Widget::TabPane(p) => p.render(gui, d, offset),
+            Widget::ScrollPane(p) => p.render(gui, d, offset),
+            Widget::ProgressBar(p) => p.render(gui, d, offset),
+            Widget::Image(p) => p.render(gui, d, offset),
+            Widget::MenuBar(p) => p.render(gui, d, offset),
+            Widget::SplitPane(p) => p.render(gui, d, offset),
+            Widget::ColorPicker(p) => p.render(gui, d, offset),
+            Widget::RangeSlider(p) => p.render(gui, d, offset),
+            Widget::Canvas(p) => p.render(gui, d, offset),
+            Widget::Chart(p) => p.render(gui, d, offset),
+            Widget::RichText(p) => p.render(gui, d, offset),
+            Widget::Grid(p) => p.render(gui, d, offset),
+            Widget::Flow(p) => p.render(gui, d, offset),
+            Widget::Stack(p) => p.render(gui, d, offset),
         }
     }
 
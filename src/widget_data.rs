@@ -5,11 +5,13 @@ use std::ops::Deref;
 use generational_arena::Index;
 use raylib::prelude::*;
 use std::ops::Add;
-use crate::alignment::{HAlignment, VAlignment};
+use crate::alignment::{Alignment, HAlignment, VAlignment};
 use crate::fill::Fill;
-use crate::fill::Fill::{Disabled, Enabled};
+use crate::fill::Fill::Disabled;
 use crate::gui::{Gui};
+use crate::menu::Menu;
 use crate::mouse::MouseState;
+use crate::overflow::Overflow;
 use crate::padding::Padding;
 use crate::size::Size;
 use crate::widget::Widget;
@@ -61,11 +63,42 @@ impl WidgetData {
         target.x += content_layout.x;
         target.y += content_layout.y;
 
-        for child_index in gui.get_widget_children(tree_index) {
-            if let Some(w) = gui.get_widget(child_index) {
+        let children_clip = self.abs_children_clip(offset);
+        if let Some(clip) = &children_clip {
+            gui.push_clip(clip);
+        }
+
+        for child_index in Self::children_by_z_index(gui, tree_index) {
+            if let Some(w) = gui.get_widget(child_index).filter(|w| w.is_visible()) {
                 w.render(gui, d, &target);
             }
         }
+
+        if children_clip.is_some() {
+            gui.pop_clip();
+        }
+    }
+
+    /// The children from the bottom to the top
+    fn children_by_z_index(gui: &Gui, tree_index: Index) -> Vec<Index> {
+        let mut children: Vec<Index> = gui.get_widget_children(tree_index).collect();
+        children.sort_by_key(|child_index| gui.get_widget(*child_index).map_or(0, |w| w.z_index()));
+        children
+    }
+
+    /// The clip of the children in absolute coordinates. A clip set by the widget itself
+    /// (like the viewport of a scroll pane) takes precedence over the overflow setting
+    fn abs_children_clip(&self, offset: &Vector2) -> Option<Rectangle> {
+        let children_clip = match (self.geometry.children_clip.get(), self.model.overflow.get()) {
+            (Some(clip), _) => Some(clip),
+            (None, Overflow::Clip) => Some(self.content_layout()),
+            (None, Overflow::Visible) => None,
+        };
+        children_clip.map(|mut clip| {
+            clip.x += offset.x;
+            clip.y += offset.y;
+            clip
+        })
     }
 }
 
@@ -136,6 +169,62 @@ impl WidgetData {
         self
     }
 
+    pub fn min_size(&self) -> Size {
+        self.model.min_size.get()
+    }
+    pub fn set_min_width(&self, gui: &Gui, width: f32) -> &WidgetData {
+        self.set_min_size(gui, self.model.min_size.get().with_width(width))
+    }
+    pub fn set_min_height(&self, gui: &Gui, height: f32) -> &WidgetData {
+        self.set_min_size(gui, self.model.min_size.get().with_height(height))
+    }
+    /// The widget is never smaller than this size, even when its parent has less space for it.
+    /// An empty dimension is not bounded
+    pub fn set_min_size(&self, gui: &Gui, size: Size) -> &WidgetData {
+        if self.model.min_size.get().eq(&size) {
+            return self;
+        }
+        self.model.min_size.set(size);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    pub fn max_size(&self) -> Size {
+        self.model.max_size.get()
+    }
+    pub fn set_max_width(&self, gui: &Gui, width: f32) -> &WidgetData {
+        self.set_max_size(gui, self.model.max_size.get().with_width(width))
+    }
+    pub fn set_max_height(&self, gui: &Gui, height: f32) -> &WidgetData {
+        self.set_max_size(gui, self.model.max_size.get().with_height(height))
+    }
+    /// The widget is never larger than this size, even when it fills its parent.
+    /// An empty dimension is not bounded
+    pub fn set_max_size(&self, gui: &Gui, size: Size) -> &WidgetData {
+        if self.model.max_size.get().eq(&size) {
+            return self;
+        }
+        self.model.max_size.set(size);
+        self.invalidate_preferred_size(gui);
+        self
+    }
+
+    /// The width bounded by the min and max widths, the min winning when they conflict
+    pub fn constrain_width(&self, width: f32) -> f32 {
+        let max_width = self.model.max_size.get().width();
+        let width = if max_width > 0.0 { width.min(max_width) } else { width };
+        width.max(self.model.min_size.get().width())
+    }
+    /// The height bounded by the min and max heights, the min winning when they conflict
+    pub fn constrain_height(&self, height: f32) -> f32 {
+        let max_height = self.model.max_size.get().height();
+        let height = if max_height > 0.0 { height.min(max_height) } else { height };
+        height.max(self.model.min_size.get().height())
+    }
+    pub fn constrain_size(&self, size: &Size) -> Size {
+        Size::new(self.constrain_width(size.width()), self.constrain_height(size.height()))
+    }
+
     pub fn padding(&self) -> Padding {
         self.model.padding.get()
     }
@@ -208,6 +297,17 @@ impl WidgetData {
         self.invalidate_position(gui);
         self
     }
+    pub fn alignment(&self) -> Alignment {
+        self.model.alignment.get()
+    }
+    /// the widget takes the whole width it is given, because it fills it or is stretched
+    pub fn stretch_width(&self) -> bool {
+        self.fill_width_enabled() || self.model.alignment.get().horizontal == HAlignment::Stretch
+    }
+    /// the widget takes the whole height it is given, because it fills it or is stretched
+    pub fn stretch_height(&self) -> bool {
+        self.fill_height_enabled() || self.model.alignment.get().vertical == VAlignment::Stretch
+    }
     pub fn set_valignment(&self, gui: &Gui, valignment: VAlignment) -> &WidgetData {
         let current_alignment = self.model.alignment.get();
         self.set_alignment(gui, valignment, current_alignment.horizontal);
@@ -230,6 +330,55 @@ impl WidgetData {
         self
     }
 
+    pub fn focusable(&self) -> bool {
+        self.model.focusable.get()
+    }
+    pub fn set_focusable(&self, focusable: bool) -> &WidgetData {
+        self.model.focusable.set(focusable);
+        self
+    }
+    pub fn has_focus(&self, gui: &Gui) -> bool {
+        self.tree_index.is_some() && gui.focused_widget().eq(&self.tree_index)
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.model.visible.get()
+    }
+    /// An invisible widget still takes part in the layout of its parent
+    pub fn set_visible(&self, visible: bool) -> &WidgetData {
+        self.model.visible.set(visible);
+        self
+    }
+
+    pub fn z_index(&self) -> i32 {
+        self.model.z_index.get()
+    }
+    /// Children are drawn from the lowest z-index to the highest, the ones with the same
+    /// z-index in the order of the children
+    pub fn set_z_index(&self, z_index: i32) -> &WidgetData {
+        self.model.z_index.set(z_index);
+        self
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.model.overflow.get()
+    }
+    pub fn set_overflow(&self, overflow: Overflow) -> &WidgetData {
+        self.model.overflow.set(overflow);
+        self
+    }
+
+    /// The menu is opened at the cursor on a right click. The widget becomes hooverable
+    pub fn set_context_menu(&self, menu: Menu) -> &WidgetData {
+        self.model.context_menu.replace(Some(Rc::new(menu)));
+        self.model.hooverable.set(true);
+        self
+    }
+    pub fn clear_context_menu(&self) -> &WidgetData {
+        self.model.context_menu.replace(None);
+        self
+    }
+
     pub fn set_clickable(&self, clickable: bool) -> &WidgetData {
         self.model.clickable.set(clickable);
         self
@@ -256,6 +405,12 @@ impl WidgetData {
         self.geometry.widget_size.borrow().size().width()
     }
 
+    pub fn children_clip(&self) -> Option<Rectangle> {
+        self.geometry.children_clip.get()
+    }
+    pub fn set_children_clip(&self, clip: Option<Rectangle>) {
+        self.geometry.children_clip.set(clip)
+    }
     pub fn content_layout(&self) -> Rectangle {
         self.geometry.content_layout.get()
     }
@@ -392,13 +547,18 @@ impl WidgetData {
 
 
     pub fn update_hoovered(&self, gui: &Gui, offset: &Vector2, mouse_position: &Vector2) -> bool {
+        self.update_hoovered_clipped(gui, offset, Some(mouse_position))
+    }
+
+    /// A mouse position of None means the mouse is outside the clip of an ancestor
+    pub(crate) fn update_hoovered_clipped(&self, gui: &Gui, offset: &Vector2, mouse_position: Option<&Vector2>) -> bool {
         let mut abs_widget_layout = self.widget_layout();
         abs_widget_layout.x += offset.x;
         abs_widget_layout.y += offset.y;
 
         let hooverable = self.hooverable();
 
-        let new_hoovered = abs_widget_layout.check_collision_point_rec(mouse_position);
+        let new_hoovered = mouse_position.is_some_and(|p| abs_widget_layout.check_collision_point_rec(p));
         let old_hoovered = self.get_hoover_state();
         self.set_hoover_state(new_hoovered && hooverable);
         let mut child_hoovered = false;
@@ -409,9 +569,19 @@ impl WidgetData {
             (Some(idx), _, _) => {
                 let padding = self.padding();
                 let child_offset = Vector2::new(abs_widget_layout.x+padding.left, abs_widget_layout.y+padding.top);
-                for child_index in gui.get_widget_children(idx) {
-                    if let Some(w) = gui.get_widget(child_index) {
-                        child_hoovered |= w.update_hoovered(gui, &child_offset, mouse_position)
+                let child_mouse_position = match self.abs_children_clip(offset) {
+                    Some(clip) => mouse_position.filter(|p| clip.check_collision_point_rec(*p)),
+                    None => mouse_position,
+                };
+                let layered = self.model.layered_children.get();
+                let mut child_mouse_position = child_mouse_position;
+                for child_index in Self::children_by_z_index(gui, idx).into_iter().rev() {
+                    if let Some(w) = gui.get_widget(child_index).filter(|w| w.is_visible()) {
+                        let hit = w.update_hoovered_clipped(gui, &child_offset, child_mouse_position);
+                        child_hoovered |= hit;
+                        if hit && layered {
+                            child_mouse_position = None;
+                        }
                     }
                 }
             }
@@ -437,6 +607,16 @@ impl WidgetData {
 
         if mouse_state.left().is_pressed() && hoovered {
             armed = clickable;
+            if let (true, Some(idx)) = (self.focusable(), self.tree_index) {
+                gui.set_focus(idx)
+            }
+        }
+
+        if mouse_state.right().is_pressed() && hoovered {
+            // the deepest widget with a context menu opens it last and wins
+            if let Some(menu) = self.model.context_menu.borrow().clone() {
+                gui.open_menu(menu, *mouse_state.mouse_position(), self.text_style(), None)
+            }
         }
 
         armed &= mouse_state.left().is_down();
@@ -444,13 +624,36 @@ impl WidgetData {
         self.state.armed.set(armed);
 
         if let Some(idx) = self.tree_index {
-            for child_index in gui.get_widget_children(idx) {
-                let content_layout = self.content_layout();
-                let child_offset = Vector2::new(content_layout.x+offset.x, content_layout.y+offset.y);
-                if let Some(w) = gui.get_widget(child_index) {
-                    w.update_action(gui,&child_offset,mouse_state);
-                }
+            // a press outside of the clip of the children, set by the widget or by
+            // its overflow, does not reach them
+            let mut press_blocked = self.abs_children_clip(offset)
+                .is_some_and(|clip| !clip.check_collision_point_rec(mouse_state.mouse_position()));
+            let without_presses = if mouse_state.is_any_pressed() { Some(mouse_state.without_presses()) } else { None };
+
+            // overlapping children are visited from the top, the first one under the mouse takes the press
+            let layered = self.model.layered_children.get();
+            let mut children = Self::children_by_z_index(gui, idx);
+            if layered {
+                children.reverse();
+            }
 
+            let content_layout = self.content_layout();
+            let child_offset = Vector2::new(content_layout.x+offset.x, content_layout.y+offset.y);
+            for child_index in children {
+                if let Some(w) = gui.get_widget(child_index).filter(|w| w.is_visible()) {
+                    let child_mouse_state = match (&without_presses, press_blocked) {
+                        (Some(without_presses), true) => without_presses,
+                        _ => mouse_state,
+                    };
+                    w.update_action(gui,&child_offset,child_mouse_state);
+
+                    if layered {
+                        let mut child_layout = w.widget_layout();
+                        child_layout.x += child_offset.x;
+                        child_layout.y += child_offset.y;
+                        press_blocked |= child_layout.check_collision_point_rec(mouse_state.mouse_position());
+                    }
+                }
             }
         }
 
@@ -509,7 +712,7 @@ impl<W: WidgetSpecific> LayoutableWidget for W {
 
     fn compute_computed_size(&self, gui: &Gui) -> Size {
         if self.widget_data().dirty_flag_dirty(DirtyFlags::PREFERRED_SIZE) {
-            let size = self.compute_size(gui);
+            let size = self.widget_data().constrain_size(&self.compute_size(gui));
             let old_size = self.widget_data().geometry.computed_size.replace(size);
             if size.ne(&old_size) {
                 self.widget_data().invalidate_content_size(gui);
@@ -529,13 +732,18 @@ impl<W: WidgetSpecific> LayoutableWidget for W {
         if content_invalid {
             let mut content_size = self.widget_data().geometry.computed_size.get();
 
-            if let Enabled { .. } = self.widget_data().model.fill_width.get() {
+            if self.widget_data().stretch_width() {
                 content_size.set_width(available_space.width())
             }
-            if let Enabled { .. } = self.widget_data().model.fill_height.get() {
+            if self.widget_data().stretch_height() {
                 content_size.set_height(available_space.height())
+            } else {
+                if let Some(height) = self.height_for_width(gui, content_size.width().min(available_space.width())) {
+                    content_size.set_height(height)
+                }
             }
             content_size.min_mut(&available_space);
+            let content_size = self.widget_data().constrain_size(&content_size);
 
             {
                 let mut content_cache = self.widget_data().geometry.widget_size.borrow_mut();
@@ -548,6 +756,12 @@ impl<W: WidgetSpecific> LayoutableWidget for W {
             self.widget_data().invalidate_position(gui);
         }
     }
+    fn compute_height_for_width(&self, gui: &Gui, width: f32) -> f32 {
+        match self.height_for_width(gui, width) {
+            Some(height) => self.widget_data().constrain_height(height),
+            None => self.compute_computed_size(gui).height(),
+        }
+    }
     fn update_child_positions(&self, gui: &Gui) {
         if self.widget_data().state.dirty_flag_clean(DirtyFlags::POSITION) {
             return;
@@ -15,6 +15,8 @@ pub struct WidgetGeometry {
     pub widget_layout: Cell<Rectangle>,
     /// The position and size of the content (same as widget_layout but without padding)
     pub content_layout: Cell<Rectangle>,
+    /// The rectangle the children are clipped to (rendering and hit testing), in the same coordinates as the content layout
+    pub children_clip: Cell<Option<Rectangle>>,
 
 }
 
@@ -25,6 +27,7 @@ impl WidgetGeometry {
             widget_size: RefCell::new(Default::default()),
             widget_layout: Cell::new(Default::default()),
             computed_size: Cell::new(Default::default()),
+            children_clip: Cell::new(None),
         }
     }
 
@@ -5,6 +5,9 @@ use crate::size::Size;
 use crate::fill::Fill::Disabled;
 use crate::alignment::Alignment;
 use crate::position::Position;
+use crate::overflow::Overflow;
+use crate::menu::Menu;
+use std::rc::Rc;
 
 pub struct WidgetModel {
 
@@ -18,19 +21,33 @@ pub struct WidgetModel {
     pub border_style_name: RefCell<String>,
 
     pub preferred_size: Cell<Size>,
+    /// the bounds of the size of the widget, an empty dimension is not bounded
+    pub min_size: Cell<Size>,
+    pub max_size: Cell<Size>,
 
     pub focusable: Cell<bool>,
     pub clickable: Cell<bool>,
     pub hooverable: Cell<bool>,
     pub disable: Cell<bool>,
+    /// an invisible widget is neither rendered nor hit tested
+    pub visible: Cell<bool>,
+    pub overflow: Cell<Overflow>,
 
     pub padding: Cell<Padding>,
 
     pub action_id: RefCell<Option<String>>,
 
+    /// the menu opened by a right click on the widget
+    pub context_menu: RefCell<Option<Rc<Menu>>>,
+
     /// alignment to the target
     pub alignment: Cell<Alignment>,
 
+    /// the children with a higher z-index are drawn over their siblings and hit tested first
+    pub z_index: Cell<i32>,
+    /// the children overlap, so only the topmost one under the mouse is hoovered
+    pub layered_children: Cell<bool>,
+
 }
 
 impl WidgetModel {
@@ -40,6 +57,8 @@ impl WidgetModel {
             fill_height: Cell::new(Disabled),
             fill_width: Cell::new(Disabled),
             preferred_size: Cell::new(Default::default()),
+            min_size: Cell::new(Default::default()),
+            max_size: Cell::new(Default::default()),
             text_style_name: RefCell::new("default".to_string()),
             back_style_name: RefCell::new("default".to_string()),
             border_style_name: RefCell::new("default".to_string()),
@@ -48,8 +67,13 @@ impl WidgetModel {
             hooverable: Cell::new(false),
             clickable: Cell::new(false),
             disable: Cell::new(false),
+            visible: Cell::new(true),
+            overflow: Cell::new(Overflow::Visible),
             padding: Cell::new(Padding::none()),
             action_id: RefCell::new(None),
+            context_menu: RefCell::new(None),
+            z_index: Cell::new(0),
+            layered_children: Cell::new(false),
         }
     }
 }
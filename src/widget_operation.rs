@@ -2,6 +2,7 @@ use raylib::prelude::*;
 
 use crate::gui::{Gui};
 use crate::mouse::MouseState;
+use crate::keyboard::KeyboardState;
 use crate::size::Size;
 use crate::widget_data::WidgetData;
 
@@ -23,8 +24,22 @@ pub trait WidgetSpecific {
     fn compute_size(&self, gui:&Gui) -> Size;
     fn compute_child_content_size(&self, gui:&Gui, available_size:Size);
     fn compute_child_positions(&self, gui:&Gui);
+    /// the height of the widget when it is given the provided width, for the widgets whose
+    /// height depends on their width like wrapped text. `None` keeps the computed height
+    fn height_for_width(&self, _gui:&Gui, _width:f32) -> Option<f32> {
+        None
+    }
+
+    /// the distance from the top of the widget to the baseline of its first line of text when
+    /// the widget has the given height, for the widgets showing text. `None` aligns the bottom
+    /// of the widget on the baseline
+    fn baseline(&self, _gui:&Gui, _height:f32) -> Option<f32> {
+        None
+    }
 
     fn update_action(&self, gui:&Gui, offset: &Vector2, mouse_state: &MouseState);
+    /// called only for the widget that has the focus
+    fn update_key_action(&self, _gui:&Gui, _keyboard_state: &KeyboardState) {}
     fn render_my_visual(&self, gui:&Gui, d:&mut impl RaylibDraw, offset:&Vector2);
 
     fn render(&self, gui: &Gui, d: &mut impl RaylibDraw, offset: &Vector2) {
@@ -46,5 +61,6 @@ pub trait WidgetSpecific {
 pub trait LayoutableWidget {
     fn compute_computed_size(&self, gui: &Gui) -> Size;
     fn update_content_size(&self, gui: &Gui, available_space: &Size);
+    fn compute_height_for_width(&self, gui: &Gui, width: f32) -> f32;
     fn update_child_positions(&self, gui:&Gui);
 }